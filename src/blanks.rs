@@ -0,0 +1,17 @@
+/// Characters treated as blanks when leading or trailing blanks of a field are ignored
+#[derive(Clone, Debug)]
+pub enum Blanks {
+    /// ASCII whitespace: space, tab, line feed, form feed and carriage return
+    Ascii,
+    /// Unicode whitespace, including for example the no-break space. This is the default
+    Unicode,
+}
+
+impl Blanks {
+    pub(crate) fn is_blank(&self, c: char) -> bool {
+        match self {
+            Blanks::Ascii => { c.is_ascii_whitespace() }
+            Blanks::Unicode => { c.is_whitespace() }
+        }
+    }
+}
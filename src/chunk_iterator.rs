@@ -9,14 +9,16 @@ pub(crate) struct Chunk {
     offset: u64,
     length: u64,
     path: PathBuf,
+    file_index: usize,
 }
 
 impl Chunk {
-    pub(crate) fn new(offset: u64, length: u64, path: PathBuf) -> Chunk {
+    pub(crate) fn new(offset: u64, length: u64, path: PathBuf, file_index: usize) -> Chunk {
         Chunk {
             offset,
             length,
             path,
+            file_index,
         }
     }
 
@@ -31,10 +33,16 @@ impl Chunk {
     pub(crate) fn path(&self) -> &PathBuf {
         &self.path
     }
+
+    /// Index of the file in the sort input files
+    pub(crate) fn file_index(&self) -> usize {
+        self.file_index
+    }
 }
 
 pub(crate) struct ChunkIterator {
     path: PathBuf,
+    file_index: usize,
     reader: BufReader<File>,
     length: u64,
     reminder: u64,
@@ -44,7 +52,7 @@ pub(crate) struct ChunkIterator {
 }
 
 impl ChunkIterator {
-    pub(crate) fn new(path: &PathBuf, jump: u64, endl: char) -> Result<ChunkIterator, anyhow::Error> {
+    pub(crate) fn new(path: &PathBuf, file_index: usize, jump: u64, endl: char) -> Result<ChunkIterator, anyhow::Error> {
         let metadata = path.metadata()
             .with_context(|| anyhow!("path: {}", path.display()))?;
         let length = metadata.len();
@@ -55,6 +63,7 @@ impl ChunkIterator {
         Ok(
             ChunkIterator {
                 path: path.clone(),
+                file_index,
                 reader: BufReader::new(file),
                 length,
                 reminder,
@@ -94,14 +103,14 @@ impl Iterator for ChunkIterator {
         if self.reminder == 0 {
             None
         } else if self.jump >= self.reminder {
-            let chunk = Chunk::new(self.pos, self.reminder, self.path.clone());
+            let chunk = Chunk::new(self.pos, self.reminder, self.path.clone(), self.file_index);
             self.pos = self.length;
             self.reminder = 0;
             Some(chunk)
         } else {
             let current = self.jump();
             let actual_jump = current - self.pos;
-            let chunk = Chunk::new(self.pos, actual_jump, self.path.clone());
+            let chunk = Chunk::new(self.pos, actual_jump, self.path.clone(), self.file_index);
             self.pos = current;
             self.reminder = self.length - current;
             Some(chunk)
@@ -122,7 +131,7 @@ mod tests {
         let jump = 20000;
         let input_path = PathBuf::from("./tests/fixtures/empty-file.dat");
         let mut count = 0;
-        let chunk_iterator = ChunkIterator::new(&input_path, jump, '\n')?;
+        let chunk_iterator = ChunkIterator::new(&input_path, 0, jump, '\n')?;
         for _chunk in chunk_iterator {
             count += 1;
         }
@@ -136,7 +145,7 @@ mod tests {
         let jump = input_path.metadata().unwrap().len() + 18;
         let mut count = 0;
         let mut lines = 0;
-        let chunk_iterator = ChunkIterator::new(&input_path, jump, '\n')?;
+        let chunk_iterator = ChunkIterator::new(&input_path, 0, jump, '\n')?;
         for chunk in chunk_iterator {
             count += 1;
             assert_eq!(chunk.offset(), 0);
@@ -155,7 +164,7 @@ mod tests {
         let jump = input_path.metadata().unwrap().len() + 18;
         let mut count = 0;
         let mut lines = 0;
-        let chunk_iterator = ChunkIterator::new(&input_path, jump, '\n')?;
+        let chunk_iterator = ChunkIterator::new(&input_path, 0, jump, '\n')?;
         for chunk in chunk_iterator {
             assert_eq!(chunk.offset(), 0);
             assert_eq!(chunk.length(), input_path.metadata().unwrap().len());
@@ -172,7 +181,7 @@ mod tests {
     fn test_no_lines_lost() -> Result<(), anyhow::Error> {
         let input_path = PathBuf::from("./tests/fixtures/sorted-10000.dat");
         let jump = 10_000;
-        let chunk_iterator = ChunkIterator::new(&input_path, jump, '\n')?;
+        let chunk_iterator = ChunkIterator::new(&input_path, 0, jump, '\n')?;
         let mut lines = 0;
         for chunk in chunk_iterator {
             assert_eq!(chunk.path(), &input_path);
@@ -0,0 +1,141 @@
+use std::cmp::Ordering;
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use icu_collator::{Collator, CollatorOptions};
+use icu_locid::Locale;
+
+/// Collation strength, the level of differences that are significant for comparison
+#[derive(Clone, Debug)]
+pub enum Strength {
+    /// Base letters only, for example `a`, `á` and `A` compare as equal
+    Primary,
+    /// Base letters and accents, for example `a` and `A` compare as equal
+    Secondary,
+    /// Base letters, accents and case. This is the default
+    Tertiary,
+    /// Also distinguishes punctuation when it is ignored at the other levels
+    Quaternary,
+    /// All differences are significant
+    Identical,
+}
+
+impl Strength {
+    fn icu_strength(&self) -> icu_collator::Strength {
+        match self {
+            Strength::Primary => { icu_collator::Strength::Primary }
+            Strength::Secondary => { icu_collator::Strength::Secondary }
+            Strength::Tertiary => { icu_collator::Strength::Tertiary }
+            Strength::Quaternary => { icu_collator::Strength::Quaternary }
+            Strength::Identical => { icu_collator::Strength::Identical }
+        }
+    }
+}
+
+/// Collation of [FieldType::String](crate::field_type::FieldType::String) fields according to
+/// the Unicode Collation Algorithm, with the root collation or the tailoring of a locale.
+/// Requires the `collation` feature, which adds the ICU collation data to the build.
+///
+/// # Examples
+/// ```
+/// use text_file_sort::collation::{Collation, Strength};
+/// use text_file_sort::field::Field;
+/// use text_file_sort::field_type::FieldType;
+/// // place names in Swedish order, where å, ä and ö follow z
+/// let field = Field::new(2, FieldType::String)
+///     .with_collation(Collation::new("sv", Strength::Tertiary).unwrap());
+/// ```
+#[derive(Clone)]
+pub struct Collation {
+    locale: String,
+    strength: Strength,
+    collator: Arc<Collator>,
+}
+
+impl Collation {
+    /// Create the root collation, the default Unicode Collation Algorithm order
+    pub fn root(strength: Strength) -> Collation {
+        Self::new("und", strength).unwrap()
+    }
+
+    /// Create the collation tailored for a BCP 47 locale, for example `de`, `sv` or
+    /// `de-u-co-phonebk`. A locale without a tailoring uses the root collation.
+    pub fn new(locale: &str, strength: Strength) -> Result<Collation, anyhow::Error> {
+        let parsed_locale = Locale::try_from_bytes(locale.as_bytes())
+            .map_err(|e| anyhow!("Invalid locale: {}, error: {}", locale, e))?;
+        let mut options = CollatorOptions::new();
+        options.strength = Some(strength.icu_strength());
+        let collator = Collator::try_new(&parsed_locale.into(), options)
+            .map_err(|e| anyhow!("Failed to create collator for locale: {}, error: {}", locale, e))?;
+        Ok(
+            Collation {
+                locale: locale.to_string(),
+                strength,
+                collator: Arc::new(collator),
+            }
+        )
+    }
+
+    /// Get the locale of this collation
+    pub fn locale(&self) -> &String {
+        &self.locale
+    }
+
+    /// Get the [Strength] of this collation
+    pub fn strength(&self) -> &Strength {
+        &self.strength
+    }
+
+    pub(crate) fn compare(&self, a: &str, b: &str) -> Ordering {
+        self.collator.compare(a, b)
+    }
+}
+
+impl Debug for Collation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Collation")
+            .field("locale", &self.locale)
+            .field("strength", &self.strength)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::collation::{Collation, Strength};
+
+    fn sorted(collation: &Collation, values: &[&str]) -> Vec<String> {
+        let mut values: Vec<String> = values.iter().map(|value| value.to_string()).collect();
+        values.sort_by(|a, b| collation.compare(a, b));
+        values
+    }
+
+    #[test]
+    fn test_root() {
+        let collation = Collation::root(Strength::Tertiary);
+        assert_eq!(
+            sorted(&collation, &["Zyrard\u{f3}w", "Z\u{fc}rich", "zebra", "Apple", "apple"]),
+            vec!["apple", "Apple", "zebra", "Z\u{fc}rich", "Zyrard\u{f3}w"]
+        );
+    }
+
+    #[test]
+    fn test_tailoring() {
+        let collation = Collation::new("sv", Strength::Tertiary).unwrap();
+        assert_eq!(
+            sorted(&collation, &["\u{c5}ngstr\u{f6}m", "Zyrard\u{f3}w", "Apple"]),
+            vec!["Apple", "Zyrard\u{f3}w", "\u{c5}ngstr\u{f6}m"]
+        );
+        assert!(Collation::new("not a locale", Strength::Tertiary).is_err());
+    }
+
+    #[test]
+    fn test_strength() {
+        let collation = Collation::root(Strength::Primary);
+        assert!(collation.compare("resume", "R\u{e9}sum\u{e9}").is_eq());
+        let collation = Collation::root(Strength::Secondary);
+        assert!(collation.compare("resume", "R\u{e9}sum\u{e9}").is_lt());
+        assert!(collation.compare("resume", "Resume").is_eq());
+    }
+}
@@ -0,0 +1,262 @@
+use std::cmp::Ordering;
+
+/// Compare versions with the semantics of GNU `sort -V` (gnulib filevercmp). Versions that are
+/// equal according to filevercmp, for example `1.01` and `1.1`, are compared as strings.
+pub(crate) fn version_cmp(a: &str, b: &str) -> Ordering {
+    filevercmp(a.as_bytes(), b.as_bytes()).then_with(|| a.cmp(b))
+}
+
+/// Compare strings so that runs of ASCII digits are compared numerically and the rest of the
+/// characters are compared lexicographically. Strings that are equal by this comparison, for
+/// example `Route 09` and `Route 9`, are compared as strings.
+pub(crate) fn natural_cmp(a: &str, b: &str) -> Ordering {
+    let (a_bytes, b_bytes) = (a.as_bytes(), b.as_bytes());
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+        if a_bytes[i].is_ascii_digit() && b_bytes[j].is_ascii_digit() {
+            let a_end = digits_end(a_bytes, i);
+            let b_end = digits_end(b_bytes, j);
+            let ordering = digits_cmp(&a_bytes[i..a_end], &b_bytes[j..b_end]);
+            if ordering != Ordering::Equal {
+                return ordering;
+            }
+            i = a_end;
+            j = b_end;
+        } else {
+            let a_char = a[i..].chars().next().unwrap();
+            let b_char = b[j..].chars().next().unwrap();
+            let ordering = a_char.cmp(&b_char);
+            if ordering != Ordering::Equal {
+                return ordering;
+            }
+            i += a_char.len_utf8();
+            j += b_char.len_utf8();
+        }
+    }
+    (a.len() - i).cmp(&(b.len() - j)).then_with(|| a.cmp(b))
+}
+
+/// Compare two runs of ASCII digits by their numeric value. Also compares lowercase hexadecimal
+/// digits.
+pub(crate) fn digits_cmp(a: &[u8], b: &[u8]) -> Ordering {
+    let a = trim_leading_zeros(a);
+    let b = trim_leading_zeros(b);
+    a.len().cmp(&b.len()).then_with(|| a.cmp(b))
+}
+
+fn trim_leading_zeros(digits: &[u8]) -> &[u8] {
+    let zeros = digits.iter().take_while(|d| **d == b'0').count();
+    &digits[zeros..]
+}
+
+fn digits_end(s: &[u8], start: usize) -> usize {
+    start + s[start..].iter().take_while(|c| c.is_ascii_digit()).count()
+}
+
+fn filevercmp(a: &[u8], b: &[u8]) -> Ordering {
+    if a == b {
+        return Ordering::Equal;
+    }
+    // special cases: "" sorts first, then ".", then "..", then other names starting with "."
+    for special in [&b""[..], b".", b".."] {
+        if a == special {
+            return Ordering::Less;
+        }
+        if b == special {
+            return Ordering::Greater;
+        }
+    }
+    match (a[0] == b'.', b[0] == b'.') {
+        (true, false) => { return Ordering::Less; }
+        (false, true) => { return Ordering::Greater; }
+        _ => {}
+    }
+
+    let a_prefix = file_prefix_len(a);
+    let b_prefix = file_prefix_len(b);
+    let ordering = verrevcmp(&a[..a_prefix], &b[..b_prefix]);
+    if ordering != Ordering::Equal || (a_prefix == a.len() && b_prefix == b.len()) {
+        ordering
+    } else {
+        verrevcmp(a, b)
+    }
+}
+
+/// Length of the name without the suffix matching `(\.[A-Za-z~][A-Za-z0-9~]*)*$`
+fn file_prefix_len(s: &[u8]) -> usize {
+    let mut prefix_len = 0;
+    let mut i = 0;
+    loop {
+        while i + 1 < s.len() && s[i] == b'.' && (s[i + 1].is_ascii_alphabetic() || s[i + 1] == b'~') {
+            i += 2;
+            while i < s.len() && (s[i].is_ascii_alphanumeric() || s[i] == b'~') {
+                i += 1;
+            }
+        }
+        if i == s.len() {
+            return prefix_len;
+        }
+        i += 1;
+        prefix_len = i;
+    }
+}
+
+fn version_char_order(s: &[u8], pos: usize) -> i32 {
+    if pos == s.len() {
+        -1
+    } else if s[pos].is_ascii_digit() {
+        0
+    } else if s[pos].is_ascii_alphabetic() {
+        s[pos] as i32
+    } else if s[pos] == b'~' {
+        -2
+    } else {
+        s[pos] as i32 + 256
+    }
+}
+
+fn verrevcmp(a: &[u8], b: &[u8]) -> Ordering {
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() || j < b.len() {
+        while (i < a.len() && !a[i].is_ascii_digit()) || (j < b.len() && !b[j].is_ascii_digit()) {
+            let a_order = version_char_order(a, i);
+            let b_order = version_char_order(b, j);
+            if a_order != b_order {
+                return a_order.cmp(&b_order);
+            }
+            i += 1;
+            j += 1;
+        }
+        let a_end = digits_end(a, i);
+        let b_end = digits_end(b, j);
+        let ordering = digits_cmp(&a[i..a_end], &b[j..b_end]);
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+        i = a_end;
+        j = b_end;
+    }
+    Ordering::Equal
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cmp::Ordering;
+
+    use crate::compare::{natural_cmp, version_cmp};
+
+    fn assert_sorted(cmp: fn(&str, &str) -> Ordering, sorted: &[&str]) {
+        let mut values: Vec<&str> = sorted.iter().rev().copied().collect();
+        values.sort_by(|a, b| cmp(a, b));
+        assert_eq!(values, sorted);
+    }
+
+    #[test]
+    fn test_version_cmp() {
+        assert_sorted(
+            version_cmp,
+            &[
+                "",
+                ".",
+                "..",
+                ".A",
+                ".Z",
+                ".a~",
+                ".a",
+                ".b~",
+                ".b",
+                ".zz~1~",
+                ".zz~1",
+                ".zz",
+                ".zz.~1~",
+                ".0",
+                ".9",
+                ".zz.0",
+                ".\x01",
+                ".\x01.txt",
+                ".\x01x",
+                ".\x01x\x01",
+                ".\x01.0",
+                "0",
+                "1.0~rc1",
+                "1.0",
+                "1.0a",
+                "1.01",
+                "1.1",
+                "2.9",
+                "2.10",
+                "9",
+                "A",
+                "Z",
+                "a~",
+                "a",
+                "a.b~",
+                "a.b",
+                "a.bc~",
+                "a.bc",
+                "a+",
+                "a.",
+                "a..a",
+                "a.+",
+                "b~",
+                "b",
+                "gcc-c++-10.fc9.tar.gz",
+                "gcc-c++-10.8.12-0.7rc2.fc9.tar.bz2",
+                "glibc-2-0.1.beta1.fc10.rpm",
+                "glibc-common-5-0.2.beta2.fc9.ebuild",
+                "glibc-common-5-0.2b.deb",
+                "glibc-common-11b.ebuild",
+                "glibc-common-11-0.6rc2.ebuild",
+                "libstdc++-0.5.8.11-0.7rc2.fc10.tar.gz",
+                "libstdc++-4a.fc8.tar.gz",
+                "libstdc++-4.10.4.20040204svn.rpm",
+                "libstdc++-devel-3.fc8.ebuild",
+                "libstdc++-devel-3a.fc9.tar.gz",
+                "libstdc++-devel-8.fc8.deb",
+                "libstdc++-devel-8.6.2-0.4b.fc8",
+                "nss_ldap-1-0.2b.fc9.tar.bz2",
+                "nss_ldap-1-0.6rc2.fc8.tar.gz",
+                "nss_ldap-1.0-0.1a.tar.gz",
+                "nss_ldap-10beta1.fc8.tar.gz",
+                "nss_ldap-10.11.8.6.20040204cvs.fc10.ebuild",
+                "z",
+                "zz~",
+                "zz",
+                "zz.~1~",
+                "zz.0",
+                "zz.0.txt",
+                "\x01",
+                "\x01.txt",
+                "\x01x",
+                "\x01x\x01",
+                "\x01.0",
+                "#\x01.b#",
+                "#.b#",
+            ],
+        );
+        assert_eq!(version_cmp("1.01", "1.1"), Ordering::Less);
+        assert_eq!(version_cmp("1.1", "1.1"), Ordering::Equal);
+    }
+
+    #[test]
+    fn test_natural_cmp() {
+        assert_sorted(
+            natural_cmp,
+            &[
+                "",
+                "Route 1",
+                "Route 09",
+                "Route 9",
+                "Route 10",
+                "Route 10a",
+                "Route 100",
+                "Route A",
+                "a1b2",
+                "a1b10",
+                "a2",
+                "a10",
+                "ä",
+            ],
+        );
+    }
+}
@@ -2,8 +2,9 @@ use std::path::PathBuf;
 use regex::Regex;
 use crate::field::Field;
 use crate::order::Order;
+use crate::unique::{Tombstone, Unique};
 
-#[derive(Clone)]
+#[derive(Clone, Debug)]
 pub(crate) struct Config {
     tmp: PathBuf,
     tmp_prefix: String,
@@ -18,9 +19,16 @@ pub(crate) struct Config {
     files: usize,
     fields: Vec<Field>,
     order: Order,
+    // order of each field, resolved once for all records
+    orders: Vec<Order>,
     prefix: Vec<String>,
     suffix: Vec<String>,
     endl: char,
+    stable: bool,
+    last_resort: bool,
+    unique: Option<Unique>,
+    priorities: Option<Vec<usize>>,
+    tombstone: Option<Tombstone>,
 }
 
 impl Config {
@@ -41,8 +49,16 @@ impl Config {
         prefix: Vec<String>,
         suffix: Vec<String>,
         endl: char,
+        stable: bool,
+        last_resort: bool,
+        unique: Option<Unique>,
+        priorities: Option<Vec<usize>>,
+        tombstone: Option<Tombstone>,
     ) -> Config {
         let queue_size = 4096;
+        let orders = fields.iter()
+            .map(|field| field.order().clone().unwrap_or(order.clone()))
+            .collect();
         Config {
             tmp,
             tmp_prefix,
@@ -57,9 +73,15 @@ impl Config {
             files,
             fields,
             order,
+            orders,
             prefix,
             suffix,
             endl,
+            stable,
+            last_resort,
+            unique,
+            priorities,
+            tombstone,
         }
     }
 
@@ -87,14 +109,6 @@ impl Config {
         self.field_separator
     }
 
-    pub(crate) fn ignore_empty(&self) -> bool {
-        self.ignore_empty
-    }
-
-    pub(crate) fn ignore_lines(&self) -> &Option<Regex> {
-        &self.ignore_lines
-    }
-
     pub(crate) fn concurrent_merge(&self) -> bool {
         self.concurrent_merge
     }
@@ -115,6 +129,11 @@ impl Config {
         &self.order
     }
 
+    /// The order of each field, the order of the field or else the order of the sort
+    pub(crate) fn orders(&self) -> &[Order] {
+        &self.orders
+    }
+
     pub(crate) fn prefix(&self) -> &Vec<String> {
         &self.prefix
     }
@@ -126,4 +145,38 @@ impl Config {
     pub(crate) fn endl(&self) -> char {
         self.endl
     }
+
+    pub(crate) fn last_resort(&self) -> bool {
+        self.last_resort
+    }
+
+    pub(crate) fn unique(&self) -> &Option<Unique> {
+        &self.unique
+    }
+
+    pub(crate) fn priorities(&self) -> &Option<Vec<usize>> {
+        &self.priorities
+    }
+
+    pub(crate) fn tombstone(&self) -> &Option<Tombstone> {
+        &self.tombstone
+    }
+
+    /// True if the line is skipped, because it is empty or matches the ignored lines
+    pub(crate) fn ignored(&self, line: &str) -> bool {
+        (self.ignore_empty && line.trim().is_empty())
+            || self.ignore_lines.as_ref().is_some_and(|r| r.is_match(line.trim()))
+    }
+
+    /// True if only one of the records with equal keys is kept
+    pub(crate) fn deduplicated(&self) -> bool {
+        self.unique.is_some() || self.priorities.is_some()
+    }
+
+    /// True if records carry their input position, to keep the input order of equal records
+    pub(crate) fn positioned(&self) -> bool {
+        self.stable
+            || self.priorities.is_some()
+            || matches!(self.unique, Some(Unique::KeepFirst) | Some(Unique::KeepLast))
+    }
 }
\ No newline at end of file
@@ -0,0 +1,103 @@
+use std::any::Any;
+use std::cmp::Ordering;
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
+
+/// Extract a user defined key from a field value
+///
+/// Implement this trait to sort by a type that is not covered by
+/// [FieldType](crate::field_type::FieldType), for example a domain identifier or a composite
+/// encoding, and register it with [Field::with_custom_type](crate::field::Field::with_custom_type).
+/// The key is used by sort, merge and check.
+///
+/// # Examples
+/// ```
+/// use text_file_sort::custom_type::KeyExtractor;
+/// use text_file_sort::field::Field;
+/// use text_file_sort::field_type::FieldType;
+///
+/// // order ticket identifiers, for example ABC-123, by project and then by number
+/// struct TicketId;
+///
+/// impl KeyExtractor for TicketId {
+///     type Key = (String, u64);
+///
+///     fn extract(&self, value: &str) -> Result<Self::Key, anyhow::Error> {
+///         let (project, number) = value.split_once('-')
+///             .ok_or_else(|| anyhow::anyhow!("Invalid ticket: {}", value))?;
+///         Ok((project.to_string(), number.parse()?))
+///     }
+/// }
+///
+/// let field = Field::new(1, FieldType::String).with_custom_type(TicketId);
+/// ```
+pub trait KeyExtractor: Send + Sync + 'static {
+    /// The type of the extracted key
+    type Key: Ord + Debug + Send + Sync + 'static;
+
+    /// Extract the key from the field value. The value is trimmed and converted to upper case if
+    /// the field ignores blanks or case. An error is handled according to the
+    /// [ParseFailure](crate::parse_failure::ParseFailure) policy of the field.
+    fn extract(&self, value: &str) -> Result<Self::Key, anyhow::Error>;
+}
+
+/// A [KeyExtractor] registered as [FieldType::Custom](crate::field_type::FieldType::Custom)
+#[derive(Clone)]
+pub struct CustomType {
+    extractor: Arc<dyn AnyExtractor>,
+}
+
+impl CustomType {
+    /// Create a new [CustomType] from a [KeyExtractor]
+    pub fn new<E: KeyExtractor>(extractor: E) -> CustomType {
+        CustomType {
+            extractor: Arc::new(extractor),
+        }
+    }
+
+    pub(crate) fn extract(&self, value: &str) -> Result<Box<dyn AnyKey>, anyhow::Error> {
+        self.extractor.extract_any(value)
+    }
+}
+
+impl Debug for CustomType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "CustomType({})", self.extractor.type_name())
+    }
+}
+
+trait AnyExtractor: Send + Sync {
+    fn extract_any(&self, value: &str) -> Result<Box<dyn AnyKey>, anyhow::Error>;
+    fn type_name(&self) -> &'static str;
+}
+
+impl<E: KeyExtractor> AnyExtractor for E {
+    fn extract_any(&self, value: &str) -> Result<Box<dyn AnyKey>, anyhow::Error> {
+        Ok(Box::new(self.extract(value)?))
+    }
+
+    fn type_name(&self) -> &'static str {
+        std::any::type_name::<E>()
+    }
+}
+
+/// A key extracted by a [KeyExtractor] with its type erased
+pub(crate) trait AnyKey: Debug + Send + Sync {
+    fn as_any(&self) -> &dyn Any;
+    fn any_cmp(&self, other: &dyn AnyKey) -> Ordering;
+}
+
+impl<K: Ord + Debug + Send + Sync + 'static> AnyKey for K {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn any_cmp(&self, other: &dyn AnyKey) -> Ordering {
+        match other.as_any().downcast_ref::<K>() {
+            Some(other) => { self.cmp(other) }
+            None => {
+                panic!("Comparing custom keys of different types: {:?}, {:?}", self, other)
+            }
+        }
+    }
+}
@@ -0,0 +1,172 @@
+use anyhow::anyhow;
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, TimeZone, Utc};
+
+const ISO_8601_WITH_OFFSET: [&str; 2] = [
+    "%Y-%m-%dT%H:%M:%S%.f%#z",
+    "%Y-%m-%d %H:%M:%S%.f%#z",
+];
+
+const ISO_8601_WITHOUT_OFFSET: [&str; 2] = [
+    "%Y-%m-%dT%H:%M:%S%.f",
+    "%Y-%m-%d %H:%M:%S%.f",
+];
+
+/// Date and time format of a [FieldType::DateTime](crate::field_type::FieldType::DateTime) field
+///
+/// Values are parsed into an instant, so values in different timezones are compared by real time.
+/// Values without an offset are interpreted in the timezone of the field.
+///
+/// # Examples
+/// ```
+/// use text_file_sort::date_time_format::DateTimeFormat;
+/// use text_file_sort::field::Field;
+/// use text_file_sort::field_type::FieldType;
+/// // pg_dump timestamp, for example 2021-02-16 08:32:36
+/// let timestamp = Field::new(3, FieldType::DateTime {
+///     format: DateTimeFormat::Pattern("%Y-%m-%d %H:%M:%S".to_string()),
+///     timezone: None,
+/// });
+/// // GTFS date, for example 20210216
+/// let date = Field::new(2, FieldType::DateTime {
+///     format: DateTimeFormat::Pattern("%Y%m%d".to_string()),
+///     timezone: None,
+/// });
+/// ```
+#[derive(Clone, Debug)]
+pub enum DateTimeFormat {
+    /// A [strftime](https://docs.rs/chrono/latest/chrono/format/strftime/index.html) pattern. The
+    /// pattern may describe a date and time with or without an offset, or a date only, in which
+    /// case the time is midnight.
+    Pattern(String),
+    /// ISO 8601 date, date and time, or date and time with an offset, for example `2021-02-16`,
+    /// `2021-02-16T08:32:36.5` or `2021-02-16 08:32:36+02`. Both `T` and space are accepted as
+    /// the date and time separator.
+    Iso8601,
+    /// Seconds since the Unix epoch, optionally with a fraction, for example `1613464356.5`
+    EpochSeconds,
+    /// Milliseconds since the Unix epoch
+    EpochMilliseconds,
+}
+
+impl DateTimeFormat {
+    pub(crate) fn parse(&self, value: &str, timezone: &Option<FixedOffset>) -> Result<DateTime<Utc>, anyhow::Error> {
+        let value = value.trim();
+        let timezone = timezone.unwrap_or(FixedOffset::east_opt(0).unwrap());
+        let result = match self {
+            DateTimeFormat::Pattern(pattern) => {
+                Self::parse_pattern(value, pattern, &timezone)
+            }
+            DateTimeFormat::Iso8601 => {
+                Self::parse_iso_8601(value, &timezone)
+            }
+            DateTimeFormat::EpochSeconds => {
+                Self::parse_epoch_seconds(value)
+            }
+            DateTimeFormat::EpochMilliseconds => {
+                value.parse::<i64>()
+                    .ok()
+                    .and_then(DateTime::from_timestamp_millis)
+            }
+        };
+        result.ok_or_else(|| anyhow!("Failed to parse date and time: {}, format: {:?}", value, self))
+    }
+
+    fn parse_pattern(value: &str, pattern: &str, timezone: &FixedOffset) -> Option<DateTime<Utc>> {
+        if let Ok(date_time) = DateTime::parse_from_str(value, pattern) {
+            Some(date_time.with_timezone(&Utc))
+        } else if let Ok(date_time) = NaiveDateTime::parse_from_str(value, pattern) {
+            Self::from_local(date_time, timezone)
+        } else if let Ok(date) = NaiveDate::parse_from_str(value, pattern) {
+            Self::from_local(date.and_hms_opt(0, 0, 0)?, timezone)
+        } else {
+            None
+        }
+    }
+
+    fn parse_iso_8601(value: &str, timezone: &FixedOffset) -> Option<DateTime<Utc>> {
+        if let Some(date_time) = ISO_8601_WITH_OFFSET.iter()
+            .find_map(|pattern| DateTime::parse_from_str(value, pattern).ok()) {
+            Some(date_time.with_timezone(&Utc))
+        } else if let Ok(date_time) = DateTime::parse_from_rfc3339(value) {
+            Some(date_time.with_timezone(&Utc))
+        } else if let Some(date_time) = ISO_8601_WITHOUT_OFFSET.iter()
+            .find_map(|pattern| NaiveDateTime::parse_from_str(value, pattern).ok()) {
+            Self::from_local(date_time, timezone)
+        } else if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+            Self::from_local(date.and_hms_opt(0, 0, 0)?, timezone)
+        } else {
+            None
+        }
+    }
+
+    fn parse_epoch_seconds(value: &str) -> Option<DateTime<Utc>> {
+        let (seconds, fraction) = value.split_once('.').unwrap_or((value, ""));
+        if !fraction.chars().all(|c| c.is_ascii_digit()) {
+            return None;
+        }
+        let seconds = seconds.parse::<i64>().ok()?;
+        let mut nanos: u32 = 0;
+        for (i, c) in fraction.chars().take(9).enumerate() {
+            nanos += c.to_digit(10)? * 10_u32.pow(8 - i as u32);
+        }
+        if value.starts_with('-') && nanos > 0 {
+            DateTime::from_timestamp(seconds - 1, 1_000_000_000 - nanos)
+        } else {
+            DateTime::from_timestamp(seconds, nanos)
+        }
+    }
+
+    fn from_local(date_time: NaiveDateTime, timezone: &FixedOffset) -> Option<DateTime<Utc>> {
+        timezone.from_local_datetime(&date_time)
+            .single()
+            .map(|date_time| date_time.with_timezone(&Utc))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, FixedOffset, Utc};
+
+    use crate::date_time_format::DateTimeFormat;
+
+    fn utc(value: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(value).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_pattern() {
+        let format = DateTimeFormat::Pattern("%Y-%m-%d %H:%M:%S".to_string());
+        assert_eq!(format.parse("2021-02-16 08:32:36", &None).unwrap(), utc("2021-02-16T08:32:36Z"));
+        let timezone = FixedOffset::east_opt(2 * 3600);
+        assert_eq!(format.parse("2021-02-16 08:32:36\n", &timezone).unwrap(), utc("2021-02-16T06:32:36Z"));
+        assert!(format.parse("2021-02-16", &None).is_err());
+
+        let format = DateTimeFormat::Pattern("%Y%m%d".to_string());
+        assert_eq!(format.parse("20210216", &None).unwrap(), utc("2021-02-16T00:00:00Z"));
+    }
+
+    #[test]
+    fn test_iso_8601() {
+        let format = DateTimeFormat::Iso8601;
+        assert_eq!(format.parse("2021-02-16T08:32:36+02:00", &None).unwrap(), utc("2021-02-16T06:32:36Z"));
+        assert_eq!(format.parse("2021-02-16 08:32:36+02", &None).unwrap(), utc("2021-02-16T06:32:36Z"));
+        assert_eq!(format.parse("2021-02-16T08:32:36.250-0130", &None).unwrap(), utc("2021-02-16T10:02:36.25Z"));
+        assert_eq!(format.parse("2021-02-16T08:32:36Z", &None).unwrap(), utc("2021-02-16T08:32:36Z"));
+        assert_eq!(format.parse("2021-02-16T08:32:36", &None).unwrap(), utc("2021-02-16T08:32:36Z"));
+        assert_eq!(format.parse("2021-02-16", &None).unwrap(), utc("2021-02-16T00:00:00Z"));
+        assert!(format.parse("16/02/2021", &None).is_err());
+    }
+
+    #[test]
+    fn test_epoch() {
+        let format = DateTimeFormat::EpochSeconds;
+        assert_eq!(format.parse("1613464356", &None).unwrap(), utc("2021-02-16T08:32:36Z"));
+        assert_eq!(format.parse("1613464356.5", &None).unwrap(), utc("2021-02-16T08:32:36.5Z"));
+        assert_eq!(format.parse("-0.5", &None).unwrap(), utc("1969-12-31T23:59:59.5Z"));
+        assert!(format.parse("1613464356.5x", &None).is_err());
+
+        let format = DateTimeFormat::EpochMilliseconds;
+        assert_eq!(format.parse("1613464356500", &None).unwrap(), utc("2021-02-16T08:32:36.5Z"));
+        assert!(format.parse("1613464356.5", &None).is_err());
+    }
+}
@@ -0,0 +1,118 @@
+use std::cmp::Ordering;
+
+use anyhow::anyhow;
+
+use crate::compare::digits_cmp;
+
+/// Exact decimal number of any length compared on its digits without conversion
+#[derive(Debug)]
+pub(crate) struct Decimal {
+    negative: bool,
+    // without leading zeros
+    integer: String,
+    // without trailing zeros
+    fraction: String,
+}
+
+impl Decimal {
+    pub(crate) fn parse(value: &str, decimal_separator: char, allow_fraction: bool) -> Result<Decimal, anyhow::Error> {
+        let (negative, unsigned) = match value.strip_prefix('-') {
+            Some(unsigned) => { (true, unsigned) }
+            None => { (false, value.strip_prefix('+').unwrap_or(value)) }
+        };
+        let (integer, fraction) = match unsigned.split_once(decimal_separator) {
+            Some((integer, fraction)) if allow_fraction => { (integer, fraction) }
+            _ => { (unsigned, "") }
+        };
+        if (integer.is_empty() && fraction.is_empty())
+            || !integer.chars().all(|c| c.is_ascii_digit())
+            || !fraction.chars().all(|c| c.is_ascii_digit()) {
+            return Err(anyhow!("Failed to parse decimal: {}", value));
+        }
+        let integer = integer.trim_start_matches('0').to_string();
+        let fraction = fraction.trim_end_matches('0').to_string();
+        let negative = negative && !(integer.is_empty() && fraction.is_empty());
+        Ok(
+            Decimal {
+                negative,
+                integer,
+                fraction,
+            }
+        )
+    }
+
+    fn magnitude_cmp(&self, other: &Self) -> Ordering {
+        digits_cmp(self.integer.as_bytes(), other.integer.as_bytes())
+            .then_with(|| self.fraction.cmp(&other.fraction))
+    }
+}
+
+impl Eq for Decimal {}
+
+impl PartialEq<Self> for Decimal {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl PartialOrd<Self> for Decimal {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Decimal {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, false) => { self.magnitude_cmp(other) }
+            (true, true) => { other.magnitude_cmp(self) }
+            (false, true) => { Ordering::Greater }
+            (true, false) => { Ordering::Less }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::decimal::Decimal;
+
+    fn decimal(value: &str) -> Decimal {
+        Decimal::parse(value, '.', true).unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        assert!(Decimal::parse("", '.', true).is_err());
+        assert!(Decimal::parse("-", '.', true).is_err());
+        assert!(Decimal::parse(".", '.', true).is_err());
+        assert!(Decimal::parse("1e3", '.', true).is_err());
+        assert!(Decimal::parse("1.5", '.', false).is_err());
+        assert!(Decimal::parse("1,5", '.', true).is_err());
+        assert_eq!(Decimal::parse("1,5", ',', true).unwrap(), decimal("1.5"));
+        assert_eq!(decimal("-0.000"), decimal("+0"));
+        assert_eq!(decimal(".5"), decimal("0.50"));
+        assert_eq!(decimal("007."), decimal("7"));
+    }
+
+    #[test]
+    fn test_cmp() {
+        let sorted = [
+            "-100000000000000000000000000001",
+            "-100000000000000000000000000000.5",
+            "-2",
+            "-1.5",
+            "-0.1",
+            "0",
+            "0.1",
+            "0.10000000000000001",
+            "0.2",
+            "9",
+            "10",
+            "9223372036854775808",
+            "100000000000000000000000000000",
+        ];
+        for (a, b) in sorted.iter().zip(sorted.iter().skip(1)) {
+            assert!(decimal(a) < decimal(b), "{} < {}", a, b);
+        }
+    }
+}
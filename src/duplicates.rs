@@ -0,0 +1,141 @@
+use std::cmp::{Ordering, Reverse};
+use std::collections::BinaryHeap;
+use std::path::{Path, PathBuf};
+
+use anyhow::anyhow;
+
+use crate::config::Config;
+use crate::line_record::LineRecord;
+use crate::record_reader::RecordReader;
+
+/// A line of a group of records with equal keys, see
+/// [Sort::check_duplicates](crate::sort::Sort::check_duplicates)
+#[derive(Clone, Debug)]
+pub struct DuplicateLine {
+    path: PathBuf,
+    line_number: usize,
+    line: String,
+}
+
+impl DuplicateLine {
+    /// Get the path of the input file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Get the line number in the input file, starting at 1
+    pub fn line_number(&self) -> usize {
+        self.line_number
+    }
+
+    /// Get the line without the line terminator
+    pub fn line(&self) -> &str {
+        &self.line
+    }
+}
+
+struct Head<'a> {
+    line_record: LineRecord,
+    file_index: usize,
+    line_number: usize,
+    config: &'a Config,
+}
+
+impl Eq for Head<'_> {}
+
+impl PartialEq<Self> for Head<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl PartialOrd<Self> for Head<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Head<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.line_record.keys_cmp(&other.line_record, self.config)
+            .then_with(|| self.file_index.cmp(&other.file_index))
+            .then_with(|| self.line_number.cmp(&other.line_number))
+    }
+}
+
+fn next_head<'a>(input: &mut RecordReader, file_index: usize, config: &'a Config) -> Result<Option<Head<'a>>, anyhow::Error> {
+    Ok(
+        input.next(config)?
+            .map(|(line_number, line_record)| {
+                Head {
+                    line_record,
+                    file_index,
+                    line_number,
+                    config,
+                }
+            })
+    )
+}
+
+/// Merge sorted input files and report every group of records with equal keys. Returns the number
+/// of groups.
+pub(crate) fn check_duplicates<F>(input_files: &[PathBuf], config: &Config, mut report: F) -> Result<usize, anyhow::Error>
+where
+    F: FnMut(&[DuplicateLine]) -> Result<(), anyhow::Error>,
+{
+    let mut inputs = Vec::with_capacity(input_files.len());
+    let mut heads = BinaryHeap::with_capacity(input_files.len());
+    for (file_index, path) in input_files.iter().enumerate() {
+        let mut input = RecordReader::new(path)?;
+        if let Some(head) = next_head(&mut input, file_index, config)? {
+            heads.push(Reverse(head));
+        }
+        inputs.push(input);
+    }
+
+    let mut groups = 0;
+    let mut group: Vec<Head> = Vec::new();
+    while let Some(Reverse(head)) = heads.pop() {
+        if let Some(next) = next_head(&mut inputs[head.file_index], head.file_index, config)? {
+            if next.line_record.keys_cmp(&head.line_record, config) == Ordering::Less {
+                return Err(
+                    anyhow!(
+                        "Input is not sorted, path: {}, line: {}",
+                        inputs[next.file_index].path().display(),
+                        next.line_number,
+                    )
+                );
+            }
+            heads.push(Reverse(next));
+        }
+        let same_group = group.last()
+            .is_some_and(|last| last.line_record.keys_cmp(&head.line_record, config) == Ordering::Equal);
+        if !same_group {
+            groups += report_group(&group, &inputs, &mut report)?;
+            group.clear();
+        }
+        group.push(head);
+    }
+    groups += report_group(&group, &inputs, &mut report)?;
+    Ok(groups)
+}
+
+fn report_group<F>(group: &[Head], inputs: &[RecordReader], report: &mut F) -> Result<usize, anyhow::Error>
+where
+    F: FnMut(&[DuplicateLine]) -> Result<(), anyhow::Error>,
+{
+    if group.len() < 2 {
+        return Ok(0);
+    }
+    let duplicates: Vec<DuplicateLine> = group.iter()
+        .map(|head| {
+            DuplicateLine {
+                path: inputs[head.file_index].path().to_path_buf(),
+                line_number: head.line_number,
+                line: head.line_record.text().to_string(),
+            }
+        })
+        .collect();
+    report(&duplicates)?;
+    Ok(1)
+}
@@ -1,4 +1,39 @@
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
+
+use regex::Regex;
+
+use crate::blanks::Blanks;
+#[cfg(feature = "collation")]
+use crate::collation::Collation;
+use crate::custom_type::{CustomType, KeyExtractor};
+use crate::field_encoding::FieldEncoding;
 use crate::field_type::FieldType;
+use crate::normalization::Normalization;
+use crate::nan::Nan;
+use crate::nulls::Nulls;
+use crate::number_format::NumberFormat;
+use crate::order::Order;
+use crate::parse_failure::ParseFailure;
+use crate::spatial::Curve;
+
+type ComputeFn = dyn Fn(&[&str]) -> Result<String, anyhow::Error> + Send + Sync;
+
+/// Function computing the value of a computed field from the fields of a record
+#[derive(Clone)]
+pub(crate) struct Compute(Arc<ComputeFn>);
+
+impl Compute {
+    pub(crate) fn value(&self, parts: &[&str]) -> Result<String, anyhow::Error> {
+        (self.0)(parts)
+    }
+}
+
+impl Debug for Compute {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Compute")
+    }
+}
 
 /// Defines a field in a line record.
 ///
@@ -22,6 +57,27 @@ pub struct Field {
     ignore_blanks: bool,
     ignore_case: bool,
     random: bool,
+    encoding: FieldEncoding,
+    null_tokens: Vec<String>,
+    nulls: Option<Nulls>,
+    order: Option<Order>,
+    number_format: NumberFormat,
+    parse_failure: ParseFailure,
+    components: Vec<Field>,
+    compute: Option<Compute>,
+    case_folding: bool,
+    normalization: Option<Normalization>,
+    ignore_accents: bool,
+    #[cfg(feature = "collation")]
+    collation: Option<Arc<Collation>>,
+    dictionary_order: bool,
+    ignore_nonprinting: bool,
+    ignore_chars: Option<Regex>,
+    ignore_leading_blanks: bool,
+    ignore_trailing_blanks: bool,
+    blanks: Blanks,
+    nan: Option<Nan>,
+    signed_zero: bool,
 }
 
 impl Field {
@@ -49,9 +105,102 @@ impl Field {
             ignore_blanks: false,
             ignore_case: false,
             random: false,
+            encoding: FieldEncoding::Plain,
+            null_tokens: vec![],
+            nulls: None,
+            order: None,
+            number_format: NumberFormat::new(),
+            parse_failure: ParseFailure::Fail,
+            components: vec![],
+            compute: None,
+            case_folding: false,
+            normalization: None,
+            ignore_accents: false,
+            #[cfg(feature = "collation")]
+            collation: None,
+            dictionary_order: false,
+            ignore_nonprinting: false,
+            ignore_chars: None,
+            ignore_leading_blanks: false,
+            ignore_trailing_blanks: false,
+            blanks: Blanks::Unicode,
+            nan: None,
+            signed_zero: false,
         }
     }
 
+    /// Create a new spatial [Field] ordering records by the position of a point on a space filling
+    /// [Curve]. Nearby points are likely to be close in the sorted output.
+    ///
+    /// # Arguments
+    /// * `latitude` - the latitude field, must be of [FieldType::Number]
+    /// * `longitude` - the longitude field, must be of [FieldType::Number]
+    /// * `curve` - the space filling curve
+    /// * `precision` - number of bits, between 1 and 32, each coordinate is quantized to
+    ///
+    /// # Examples
+    /// ```
+    /// use text_file_sort::field::Field;
+    /// use text_file_sort::field_type::FieldType;
+    /// use text_file_sort::spatial::Curve;
+    /// let field = Field::new_spatial(
+    ///     Field::new(2, FieldType::Number),
+    ///     Field::new(3, FieldType::Number),
+    ///     Curve::Hilbert,
+    ///     16,
+    /// );
+    /// ```
+    pub fn new_spatial(latitude: Field, longitude: Field, curve: Curve, precision: u8) -> Field {
+        let mut field = Field::new(0, FieldType::Spatial { curve, precision });
+        field.components = vec![latitude, longitude];
+        field
+    }
+
+    /// Create a new computed [Field] whose value is computed from the fields of a record, for
+    /// example a concatenation of two fields, the length of a field or the first non empty of
+    /// two fields. The computed value is compared according to `field_type` and all the other
+    /// settings of the field.
+    ///
+    /// # Arguments
+    /// * `field_type` - the type of the computed value. See [FieldType] for supported types
+    /// * `compute` - function computing the value from the fields of a record. The first element
+    ///   of the slice is the field with index 1. An error fails the sort.
+    ///
+    /// # Examples
+    /// ```
+    /// use anyhow::anyhow;
+    /// use text_file_sort::field::Field;
+    /// use text_file_sort::field_type::FieldType;
+    /// fn part<'a>(parts: &[&'a str], index: usize) -> Result<&'a str, anyhow::Error> {
+    ///     parts.get(index - 1).copied().ok_or_else(|| anyhow!("Missing field: {}", index))
+    /// }
+    /// // concatenation of the first two fields
+    /// let concatenation = Field::new_computed(FieldType::String, |parts| {
+    ///     Ok(format!("{}{}", part(parts, 1)?, part(parts, 2)?))
+    /// });
+    /// // length of the third field, 0 if the field is missing
+    /// let length = Field::new_computed(FieldType::Integer, |parts| {
+    ///     Ok(parts.get(2).map(|part| part.chars().count()).unwrap_or(0).to_string())
+    /// });
+    /// // lower case prefix of the second field
+    /// let prefix = Field::new_computed(FieldType::String, |parts| {
+    ///     Ok(part(parts, 2)?.chars().take(3).collect::<String>().to_lowercase())
+    /// });
+    /// // coalesce(field3, field5)
+    /// let coalesce = Field::new_computed(FieldType::Integer, |parts| {
+    ///     let value = if part(parts, 3)?.is_empty() { part(parts, 5)? } else { part(parts, 3)? };
+    ///     Ok(value.to_string())
+    /// });
+    /// ```
+    pub fn new_computed<F>(field_type: FieldType, compute: F) -> Field
+    where
+        F: Fn(&[&str]) -> Result<String, anyhow::Error> + Send + Sync + 'static,
+    {
+        let mut field = Field::new(0, field_type);
+        field.compute = Some(Compute(Arc::new(compute)));
+        field
+    }
+
     /// Get the name for this field.
     pub fn name(&self) -> &String {
         &self.name
@@ -72,6 +221,21 @@ impl Field {
         self.ignore_blanks
     }
 
+    /// Get the ignore leading blanks setting for this field
+    pub fn ignore_leading_blanks(&self) -> bool {
+        self.ignore_leading_blanks
+    }
+
+    /// Get the ignore trailing blanks setting for this field
+    pub fn ignore_trailing_blanks(&self) -> bool {
+        self.ignore_trailing_blanks
+    }
+
+    /// Get the [Blanks] setting for this field
+    pub fn blanks(&self) -> &Blanks {
+        &self.blanks
+    }
+
     /// Get the ignore case setting for this field.
     pub fn ignore_case(&self) -> bool {
         self.ignore_case
@@ -82,6 +246,96 @@ impl Field {
         self.random
     }
 
+    /// Get the [FieldEncoding] for this field.
+    pub fn encoding(&self) -> &FieldEncoding {
+        &self.encoding
+    }
+
+    /// Get the tokens representing NULL for this field.
+    pub fn null_tokens(&self) -> &Vec<String> {
+        &self.null_tokens
+    }
+
+    /// Get the [Nulls] setting for this field.
+    pub fn nulls(&self) -> &Option<Nulls> {
+        &self.nulls
+    }
+
+    /// Get the [Nan] setting for this field.
+    pub fn nan(&self) -> &Option<Nan> {
+        &self.nan
+    }
+
+    /// Get the signed zero setting for this field.
+    pub fn signed_zero(&self) -> bool {
+        self.signed_zero
+    }
+
+    /// Get the [Order] for this field. None means the order of the sort is used.
+    pub fn order(&self) -> &Option<Order> {
+        &self.order
+    }
+
+    /// Get the decimal separator for this field.
+    pub fn decimal_separator(&self) -> char {
+        self.number_format.decimal_separator()
+    }
+
+    /// Get the [NumberFormat] for this field.
+    pub fn number_format(&self) -> &NumberFormat {
+        &self.number_format
+    }
+
+    /// Get the [ParseFailure] policy for this field.
+    pub fn parse_failure(&self) -> &ParseFailure {
+        &self.parse_failure
+    }
+
+    /// Get the fields a composite field, such as a spatial field, is computed from.
+    pub fn components(&self) -> &Vec<Field> {
+        &self.components
+    }
+
+    /// Get the case folding setting for this field.
+    pub fn case_folding(&self) -> bool {
+        self.case_folding
+    }
+
+    /// Get the [Normalization] for this field.
+    pub fn normalization(&self) -> &Option<Normalization> {
+        &self.normalization
+    }
+
+    /// Get the ignore accents setting for this field.
+    pub fn ignore_accents(&self) -> bool {
+        self.ignore_accents
+    }
+
+    /// Get the [Collation] for this field.
+    #[cfg(feature = "collation")]
+    pub fn collation(&self) -> &Option<Arc<Collation>> {
+        &self.collation
+    }
+
+    /// Get the dictionary order setting for this field.
+    pub fn dictionary_order(&self) -> bool {
+        self.dictionary_order
+    }
+
+    /// Get the ignore nonprinting setting for this field.
+    pub fn ignore_nonprinting(&self) -> bool {
+        self.ignore_nonprinting
+    }
+
+    /// Get the regular expression matching the characters ignored for this field.
+    pub fn ignore_chars(&self) -> &Option<Regex> {
+        &self.ignore_chars
+    }
+
+    pub(crate) fn compute(&self) -> &Option<Compute> {
+        &self.compute
+    }
+
     /// Specify a name for this field
     pub fn with_name(mut self, name: String) -> Field {
         self.name = name;
@@ -107,13 +361,34 @@ impl Field {
         self
     }
 
-    /// Specify whether to ignore blanks for comparison. When true the field will be trimmed before
-    /// comparison.
+    /// Specify whether to ignore blanks for comparison. When true both leading and trailing blanks
+    /// of the field will be trimmed before comparison.
     pub fn with_ignore_blanks(mut self, ignore_blanks: bool) -> Field {
         self.ignore_blanks = ignore_blanks;
         self
     }
 
+    /// Specify whether to ignore leading blanks for comparison, similar to GNU `sort -b`. Trailing
+    /// blanks remain significant.
+    pub fn with_ignore_leading_blanks(mut self, ignore_leading_blanks: bool) -> Field {
+        self.ignore_leading_blanks = ignore_leading_blanks;
+        self
+    }
+
+    /// Specify whether to ignore trailing blanks for comparison. Leading blanks remain
+    /// significant.
+    pub fn with_ignore_trailing_blanks(mut self, ignore_trailing_blanks: bool) -> Field {
+        self.ignore_trailing_blanks = ignore_trailing_blanks;
+        self
+    }
+
+    /// Specify the characters treated as blanks when blanks are ignored. See [Blanks]. The
+    /// default is [Blanks::Unicode].
+    pub fn with_blanks(mut self, blanks: Blanks) -> Field {
+        self.blanks = blanks;
+        self
+    }
+
     /// Specify whether to ignore case for comparison.
     pub fn with_ignore_case(mut self, ignore_case: bool) -> Field {
         self.ignore_case = ignore_case;
@@ -126,4 +401,154 @@ impl Field {
         self.random = random;
         self
     }
+
+    /// Specify the encoding used to decode the field value before comparison. See [FieldEncoding]
+    /// for supported encodings. The output line is not affected.
+    pub fn with_encoding(mut self, encoding: FieldEncoding) -> Field {
+        self.encoding = encoding;
+        self
+    }
+
+    /// Specify the tokens representing NULL, for example `\N`, `NULL` or an empty string. A field
+    /// equal to one of the tokens, ignoring the line terminator, is compared as NULL regardless of
+    /// its [FieldType]. Surrounding blanks are ignored for non String fields and for String fields
+    /// that ignore blanks.
+    pub fn with_null_tokens(mut self, null_tokens: Vec<String>) -> Field {
+        self.null_tokens = null_tokens;
+        self
+    }
+
+    /// Specify the position of NULL values. See [Nulls]. By default NULL values are greater than
+    /// any other value, that is last in ascending and first in descending order, same as in
+    /// PostgreSQL.
+    pub fn with_nulls(mut self, nulls: Nulls) -> Field {
+        self.nulls = Some(nulls);
+        self
+    }
+
+    /// Specify the position of NaN values of [FieldType::Number] fields. See [Nan]. By default NaN
+    /// values are less than any other number. When set on a coordinate of a spatial field, points
+    /// with a NaN coordinate take the position of NaN, otherwise they fail to sort.
+    pub fn with_nan(mut self, nan: Nan) -> Field {
+        self.nan = Some(nan);
+        self
+    }
+
+    /// Specify whether `-0.0` is less than `0.0` for [FieldType::Number] fields. By default they
+    /// compare as equal.
+    pub fn with_signed_zero(mut self, signed_zero: bool) -> Field {
+        self.signed_zero = signed_zero;
+        self
+    }
+
+    /// Specify the [Order] for this field, overriding the order of the sort. For example, to sort
+    /// by the first field ascending and then by the second field descending.
+    ///
+    /// # Examples
+    /// ```
+    /// use text_file_sort::field::Field;
+    /// use text_file_sort::field_type::FieldType;
+    /// use text_file_sort::order::Order;
+    /// use text_file_sort::sort::Sort;
+    /// let mut text_file_sort = Sort::new(vec![], Default::default());
+    /// text_file_sort.add_field(Field::new(1, FieldType::Integer));
+    /// text_file_sort.add_field(Field::new(2, FieldType::Integer).with_order(Order::Desc));
+    /// ```
+    pub fn with_order(mut self, order: Order) -> Field {
+        self.order = Some(order);
+        self
+    }
+
+    /// Specify the decimal separator for numeric fields. The default is '.'. Same as setting the
+    /// decimal separator of the [NumberFormat].
+    pub fn with_decimal_separator(mut self, decimal_separator: char) -> Field {
+        self.number_format = self.number_format.with_decimal_separator(decimal_separator);
+        self
+    }
+
+    /// Specify the [NumberFormat] for numeric fields, for example decimal comma and grouping
+    /// separators. The default is the plain format accepted by Rust, such as `-1234.5`.
+    pub fn with_number_format(mut self, number_format: NumberFormat) -> Field {
+        self.number_format = number_format;
+        self
+    }
+
+    /// Specify the policy for values that fail to parse. See [ParseFailure]. The default is
+    /// [ParseFailure::Fail]
+    pub fn with_parse_failure(mut self, parse_failure: ParseFailure) -> Field {
+        self.parse_failure = parse_failure;
+        self
+    }
+
+    /// Specify whether to apply full Unicode case folding to [FieldType::String] fields for
+    /// comparison, for example `ß` and `SS` compare as equal. Unlike
+    /// [Field::with_ignore_case] it handles characters that fold to several characters.
+    pub fn with_case_folding(mut self, case_folding: bool) -> Field {
+        self.case_folding = case_folding;
+        self
+    }
+
+    /// Specify the Unicode [Normalization] applied to [FieldType::String] fields for comparison.
+    /// Normalization is applied after case folding.
+    pub fn with_normalization(mut self, normalization: Normalization) -> Field {
+        self.normalization = Some(normalization);
+        self
+    }
+
+    /// Specify whether to ignore accents of [FieldType::String] fields for comparison, for
+    /// example `é` and `e` compare as equal. Accents are the combining marks of the canonical
+    /// decomposition of the value.
+    pub fn with_ignore_accents(mut self, ignore_accents: bool) -> Field {
+        self.ignore_accents = ignore_accents;
+        self
+    }
+
+    /// Specify the [Collation] of [FieldType::String] fields. By default strings are compared by
+    /// code point. The collation is applied after the other string settings of the field.
+    /// Requires the `collation` feature.
+    #[cfg(feature = "collation")]
+    pub fn with_collation(mut self, collation: Collation) -> Field {
+        self.collation = Some(Arc::new(collation));
+        self
+    }
+
+    /// Specify whether to consider only alphanumeric characters and blanks of [FieldType::String]
+    /// fields for comparison, similar to GNU `sort -d`.
+    pub fn with_dictionary_order(mut self, dictionary_order: bool) -> Field {
+        self.dictionary_order = dictionary_order;
+        self
+    }
+
+    /// Specify whether to ignore nonprinting characters of [FieldType::String] fields for
+    /// comparison, similar to GNU `sort -i`. Nonprinting characters are control characters and
+    /// invisible format characters, such as the zero width space, the soft hyphen and the byte
+    /// order mark.
+    pub fn with_ignore_nonprinting(mut self, ignore_nonprinting: bool) -> Field {
+        self.ignore_nonprinting = ignore_nonprinting;
+        self
+    }
+
+    /// Specify a regular expression matching characters of [FieldType::String] fields that are
+    /// ignored for comparison. Every match is removed from the value.
+    ///
+    /// # Examples
+    /// ```
+    /// use regex::Regex;
+    /// use text_file_sort::field::Field;
+    /// use text_file_sort::field_type::FieldType;
+    /// // ignore punctuation
+    /// let field = Field::new(2, FieldType::String)
+    ///     .with_ignore_chars(Regex::new(r"\p{P}").unwrap());
+    /// ```
+    pub fn with_ignore_chars(mut self, ignore_chars: Regex) -> Field {
+        self.ignore_chars = Some(ignore_chars);
+        self
+    }
+
+    /// Compare this field by a user defined key, see [KeyExtractor]. Replaces the [FieldType] of
+    /// the field with [FieldType::Custom].
+    pub fn with_custom_type<E: KeyExtractor>(mut self, extractor: E) -> Field {
+        self.field_type = FieldType::Custom(CustomType::new(extractor));
+        self
+    }
 }
@@ -0,0 +1,113 @@
+use std::borrow::Cow;
+
+/// Field encoding
+///
+/// The encoding is used to decode the field value before it is converted to a key for comparison.
+/// The output line is never modified.
+#[derive(Clone, Debug)]
+pub enum FieldEncoding {
+    /// The field is used as is
+    Plain,
+    /// PostgreSQL COPY text format as produced by pg_dump. Backslash escape sequences are
+    /// decoded and `\N` is treated as NULL. NULL sorts after all other values, unless the order is
+    /// descending.
+    CopyText,
+}
+
+impl FieldEncoding {
+    /// Decode the field. Returns None if the field represents NULL.
+    pub(crate) fn decode<'a>(&self, field: &'a str) -> Option<Cow<'a, str>> {
+        match self {
+            FieldEncoding::Plain => {
+                Some(Cow::Borrowed(field))
+            }
+            FieldEncoding::CopyText => {
+                if field == "\\N" {
+                    None
+                } else if !field.contains('\\') {
+                    Some(Cow::Borrowed(field))
+                } else {
+                    Some(Cow::Owned(Self::unescape_copy_text(field)))
+                }
+            }
+        }
+    }
+
+    fn unescape_copy_text(field: &str) -> String {
+        let bytes = field.as_bytes();
+        let mut result: Vec<u8> = Vec::with_capacity(bytes.len());
+        let mut i = 0;
+        while i < bytes.len() {
+            if bytes[i] != b'\\' || i + 1 == bytes.len() {
+                result.push(bytes[i]);
+                i += 1;
+                continue;
+            }
+            i += 1;
+            match bytes[i] {
+                b'b' => { result.push(0x08); i += 1; }
+                b'f' => { result.push(0x0c); i += 1; }
+                b'n' => { result.push(b'\n'); i += 1; }
+                b'r' => { result.push(b'\r'); i += 1; }
+                b't' => { result.push(b'\t'); i += 1; }
+                b'v' => { result.push(0x0b); i += 1; }
+                b'0'..=b'7' => {
+                    let mut value: u32 = 0;
+                    let mut digits = 0;
+                    while digits < 3 && i < bytes.len() && (b'0'..=b'7').contains(&bytes[i]) {
+                        value = value * 8 + (bytes[i] - b'0') as u32;
+                        digits += 1;
+                        i += 1;
+                    }
+                    result.push(value as u8);
+                }
+                b'x' if i + 1 < bytes.len() && bytes[i + 1].is_ascii_hexdigit() => {
+                    i += 1;
+                    let mut value: u32 = 0;
+                    let mut digits = 0;
+                    while digits < 2 && i < bytes.len() && bytes[i].is_ascii_hexdigit() {
+                        value = value * 16 + (bytes[i] as char).to_digit(16).unwrap();
+                        digits += 1;
+                        i += 1;
+                    }
+                    result.push(value as u8);
+                }
+                _ => {
+                    // any other escaped character, including a backslash, stands for itself
+                    let len = field[i..].chars().next().unwrap().len_utf8();
+                    result.extend_from_slice(&bytes[i..i + len]);
+                    i += len;
+                }
+            }
+        }
+        String::from_utf8_lossy(&result).into_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::field_encoding::FieldEncoding;
+
+    #[test]
+    fn test_plain() {
+        assert_eq!(FieldEncoding::Plain.decode("a\\tb\n").unwrap(), "a\\tb\n");
+        assert_eq!(FieldEncoding::Plain.decode("\\N").unwrap(), "\\N");
+    }
+
+    #[test]
+    fn test_copy_text_null() {
+        assert!(FieldEncoding::CopyText.decode("\\N").is_none());
+        assert_eq!(FieldEncoding::CopyText.decode("\\\\N").unwrap(), "\\N");
+        assert_eq!(FieldEncoding::CopyText.decode("").unwrap(), "");
+    }
+
+    #[test]
+    fn test_copy_text_escapes() {
+        assert_eq!(FieldEncoding::CopyText.decode("a\\tb\\nc\\\\d").unwrap(), "a\tb\nc\\d");
+        assert_eq!(FieldEncoding::CopyText.decode("\\b\\f\\r\\v").unwrap(), "\x08\x0c\r\x0b");
+        assert_eq!(FieldEncoding::CopyText.decode("\\101\\x42\\7").unwrap(), "AB\x07");
+        assert_eq!(FieldEncoding::CopyText.decode("\\303\\251").unwrap(), "é");
+        assert_eq!(FieldEncoding::CopyText.decode("\\é\\").unwrap(), "é\\");
+        assert_eq!(FieldEncoding::CopyText.decode("\\xg").unwrap(), "xg");
+    }
+}
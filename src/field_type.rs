@@ -1,3 +1,14 @@
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+use anyhow::Context;
+use chrono::FixedOffset;
+
+use crate::custom_type::CustomType;
+use crate::date_time_format::DateTimeFormat;
+use crate::spatial::Curve;
+
 /// Field type
 #[derive(Clone, Debug)]
 pub enum FieldType {
@@ -5,6 +16,88 @@ pub enum FieldType {
     String,
     /// Signed 64 bit integer
     Integer,
-    /// 64 bit floating point number
+    /// 64 bit floating point number. Infinity is spelled `inf` or `infinity` and NaN is spelled
+    /// `nan`, case insensitive and optionally signed, for example `-inf`, `+Infinity` or `NaN`.
+    /// See [Field::with_nan](crate::field::Field::with_nan) and
+    /// [Field::with_signed_zero](crate::field::Field::with_signed_zero) for the order of NaN and
+    /// zero values.
     Number,
+    /// Unsigned 64 bit integer
+    UnsignedInteger,
+    /// Signed 128 bit integer
+    Integer128,
+    /// Unsigned 128 bit integer
+    UnsignedInteger128,
+    /// Hexadecimal integer of any length, with or without a `0x` prefix, for example `0x1f3a` or
+    /// a hex digest. Case and leading zeros are ignored.
+    HexInteger,
+    /// Date and time compared as an instant. See [DateTimeFormat] for supported formats. The
+    /// timezone is used for values without an offset, the default is UTC.
+    DateTime {
+        format: DateTimeFormat,
+        timezone: Option<FixedOffset>,
+    },
+    /// Version string compared like GNU `sort -V`, for example `1.9` < `1.10` < `1.10a`
+    Version,
+    /// String where runs of digits are compared numerically and the rest lexicographically, for
+    /// example `Route 9` < `Route 10`
+    Natural,
+    /// Human readable size compared by magnitude, similar to GNU `sort -h`, for example `512`,
+    /// `1.5K`, `20M` or `3GiB`. Suffixes K, M, G, T, P, E, Z, Y, R and Q are case insensitive.
+    /// Bare suffixes, such as K, and IEC suffixes, such as Ki or KiB, are powers of 1024 as in the
+    /// output of `du -h` and `ls -lh`. SI suffixes followed by B, such as KB, are powers of 1000.
+    /// The number may have an exponent, for example `1.5e3` or `2e-3G`.
+    HumanSize,
+    /// Integer of any length compared on its digits, for example 20+ digit identifiers. Signs and
+    /// leading zeros are supported.
+    BigInteger,
+    /// Exact decimal number of any length compared on its digits, for example monetary values.
+    /// Signs, leading zeros and trailing zeros are supported. The decimal separator is set by
+    /// [Field::with_decimal_separator](crate::field::Field::with_decimal_separator).
+    Decimal,
+    /// IPv4 or IPv6 address compared by address. IPv4 addresses precede IPv6 addresses.
+    IpAddr,
+    /// IPv4 or IPv6 network in CIDR notation, for example `10.0.0.0/8`, compared by network and
+    /// then by prefix length. Host bits are ignored and an address without a prefix length is
+    /// a single host network.
+    IpNetwork,
+    /// Value compared by its position in the list, for example `critical`, `high`, `medium`,
+    /// `low`. Values not in the list are handled according to the
+    /// [ParseFailure](crate::parse_failure::ParseFailure) policy of the field.
+    Ranked(Vec<String>),
+    /// English month name compared like GNU `sort -M`. The month is identified case
+    /// insensitively by the first three letters of the value, for example `Jan`, `Sept`,
+    /// `December` or `Janxyz`. Unknown values precede January.
+    Month,
+    /// English weekday name, Monday first. The weekday is identified case insensitively by the
+    /// first three letters of the value, for example `Mon`, `Tues` or `Sunday`. Unknown values
+    /// precede Monday.
+    Weekday,
+    /// Position of a latitude and longitude point on a space filling [Curve]. Latitude and
+    /// longitude are quantized to `precision` bits each, between 1 and 32. Use
+    /// [Field::new_spatial](crate::field::Field::new_spatial) to create a spatial field.
+    Spatial {
+        curve: Curve,
+        precision: u8,
+    },
+    /// User defined key type. See [KeyExtractor](crate::custom_type::KeyExtractor) and
+    /// [Field::with_custom_type](crate::field::Field::with_custom_type).
+    Custom(CustomType),
+}
+
+impl FieldType {
+    /// Create a [FieldType::Ranked] from a file containing one value per line. Empty lines are
+    /// ignored.
+    pub fn ranked_from_file(path: &Path) -> Result<FieldType, anyhow::Error> {
+        let file = File::open(path).with_context(|| format!("path: {}", path.display()))?;
+        let mut values = Vec::new();
+        for line in BufReader::new(file).lines() {
+            let line = line?;
+            let value = line.strip_suffix('\r').unwrap_or(line.as_str());
+            if !value.is_empty() {
+                values.push(value.to_string());
+            }
+        }
+        Ok(FieldType::Ranked(values))
+    }
 }
@@ -1,110 +1,637 @@
+use std::borrow::Cow;
 use std::cmp::Ordering;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
 use std::str::FromStr;
+#[cfg(feature = "collation")]
+use std::sync::Arc;
 
+use anyhow::anyhow;
+use chrono::{DateTime, Utc};
 use data_encoding::HEXLOWER;
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
 
+#[cfg(feature = "collation")]
+use crate::collation::Collation;
+use crate::compare::{digits_cmp, natural_cmp, version_cmp};
+use crate::custom_type::AnyKey;
+use crate::decimal::Decimal;
 use crate::field::Field;
 use crate::field_type::FieldType;
+use crate::nan::Nan;
+use crate::nulls::Nulls;
+use crate::number_format::NumberFormat;
+use crate::order::Order;
+use crate::parse_failure::ParseFailure;
+
+const MONTHS: [&str; 12] = [
+    "january", "february", "march", "april", "may", "june",
+    "july", "august", "september", "october", "november", "december",
+];
+
+const WEEKDAYS: [&str; 7] = ["monday", "tuesday", "wednesday", "thursday", "friday", "saturday", "sunday"];
 
 #[derive(Debug)]
 pub(crate) enum Key {
     String {
         s: String
     },
+    #[cfg(feature = "collation")]
+    Collated {
+        s: String,
+        collation: Arc<Collation>,
+    },
     Integer {
         i: i64
     },
     Number {
         n: f64
     },
+    UnsignedInteger {
+        u: u128
+    },
+    Integer128 {
+        i: i128
+    },
+    HexInteger {
+        h: String
+    },
+    DateTime {
+        t: DateTime<Utc>
+    },
+    Version {
+        s: String
+    },
+    Natural {
+        s: String
+    },
+    HumanSize {
+        n: f64
+    },
+    // boxed to keep the other keys small
+    Decimal {
+        d: Box<Decimal>
+    },
+    Ranked {
+        r: usize
+    },
+    IpAddr {
+        a: IpAddr
+    },
+    IpNetwork {
+        network: IpAddr,
+        prefix: u8,
+    },
+    Spatial {
+        d: u64
+    },
+    Custom {
+        k: Box<dyn AnyKey>
+    },
+    // NaN with a position specified by the field
+    Nan {
+        first: bool,
+    },
+    // a value that failed to parse, its position is specified by the ParseFailure policy
+    Invalid {
+        s: String,
+        first: bool,
+    },
+    // NULL is greater than any other value unless its position is specified
+    Null {
+        nulls: Option<Nulls>
+    },
 }
 
 impl Key {
     pub(crate) fn new(field: &str, field_def: &Field) -> Result<Key, anyhow::Error> {
+        if field_def.random() {
+            return Ok(Self::random(field_def.field_type()));
+        }
+        match field_def.encoding().decode(field) {
+            Some(value) if !Self::is_null_token(value.as_ref(), field_def) => {
+                Self::from_value(value.as_ref(), field_def)
+                    .or_else(|e| Self::on_parse_failure(value.as_ref(), field_def, e))
+            }
+            _ => {
+                Ok(
+                    Key::Null {
+                        nulls: field_def.nulls().clone()
+                    }
+                )
+            }
+        }
+    }
+
+    /// Create a key for a composite field from the keys of its components
+    pub(crate) fn from_components(components: Vec<Key>, field_def: &Field) -> Result<Key, anyhow::Error> {
+        if field_def.random() {
+            return Ok(Self::random(field_def.field_type()));
+        }
         match field_def.field_type() {
-            FieldType::String => {
-                let mut key = field.to_string();
-                if field_def.ignore_blanks() {
-                    key = key.trim().to_string();
+            FieldType::Spatial { curve, precision } => {
+                match components.as_slice() {
+                    [Key::Number { n: latitude }, Key::Number { n: longitude }] => {
+                        Ok(
+                            Key::Spatial {
+                                d: curve.index(*latitude, *longitude, *precision)?
+                            }
+                        )
+                    }
+                    [Key::Null { .. }, _] | [_, Key::Null { .. }] => {
+                        Ok(
+                            Key::Null {
+                                nulls: field_def.nulls().clone()
+                            }
+                        )
+                    }
+                    // a coordinate component with a NaN position, see Field::with_nan
+                    [Key::Nan { first }, _] | [_, Key::Nan { first }] => {
+                        Ok(
+                            Key::Nan {
+                                first: *first
+                            }
+                        )
+                    }
+                    [Key::Invalid { s, first }, _] | [_, Key::Invalid { s, first }] => {
+                        Ok(
+                            Key::Invalid {
+                                s: s.clone(),
+                                first: *first,
+                            }
+                        )
+                    }
+                    _ => {
+                        Err(anyhow!("Spatial field requires latitude and longitude fields of FieldType::Number"))
+                    }
                 }
+            }
+            field_type => {
+                Err(anyhow!("Field type {:?} is not a composite field type", field_type))
+            }
+        }
+    }
 
-                if field_def.ignore_case() {
-                    key = key.to_uppercase()
-                }
+    /// True if the key position is not affected by the [Order]
+    pub(crate) fn has_fixed_position(&self) -> bool {
+        matches!(self, Key::Null { nulls: Some(_) } | Key::Invalid { .. } | Key::Nan { .. })
+    }
 
-                if field_def.random() {
-                    key = HEXLOWER.encode(&rand::random::<[u8; 16]>());
-                }
+    /// Compare the keys in the order of the field. Keys with a fixed position are not affected by
+    /// the order. NULL without a specified position is greater than any other value, so it is
+    /// last in ascending and first in descending order, also relative to keys with a fixed
+    /// position.
+    pub(crate) fn order_cmp(&self, other: &Self, order: &Order) -> Ordering {
+        let fixed_position = match (self, other) {
+            (Key::Null { nulls: None }, _) | (_, Key::Null { nulls: None }) => { false }
+            _ => { self.has_fixed_position() || other.has_fixed_position() }
+        };
+        let ordering = self.cmp(other);
+        match order {
+            Order::Desc if !fixed_position => { ordering.reverse() }
+            _ => { ordering }
+        }
+    }
 
+    fn on_parse_failure(field: &str, field_def: &Field, e: anyhow::Error) -> Result<Key, anyhow::Error> {
+        match field_def.parse_failure() {
+            ParseFailure::Fail => {
+                Err(e)
+            }
+            ParseFailure::First | ParseFailure::Last => {
                 Ok(
-                    Key::String {
-                        s: key
+                    Key::Invalid {
+                        s: field.to_string(),
+                        first: matches!(field_def.parse_failure(), ParseFailure::First),
                     }
                 )
             }
-            FieldType::Integer => {
-                let mut key = i64::from_str(field.trim())?;
-                if field_def.random() {
-                    key = rand::random::<i64>()
-                }
-
+            ParseFailure::Null => {
                 Ok(
-                    Key::Integer {
-                        i: key
+                    Key::Null {
+                        nulls: field_def.nulls().clone()
                     }
                 )
             }
-            FieldType::Number => {
-                let mut key = f64::from_str(field.trim())?;
-                if field_def.random() {
-                    key = rand::random::<f64>()
+            ParseFailure::Zero => {
+                Self::from_value("0", field_def).map_err(|_| e)
+            }
+            ParseFailure::Prefix => {
+                // the prefix is in the plain number format
+                let prefix = Self::numeric_prefix(field, field_def);
+                let plain = NumberFormat::new();
+                if prefix.is_empty() || prefix == "-" || prefix == "+" {
+                    Self::from_formatted_value("0", field_def, &plain).map_err(|_| e)
+                } else {
+                    Self::from_formatted_value(&prefix, field_def, &plain).map_err(|_| e)
                 }
+            }
+        }
+    }
 
-                Ok(
-                    Key::Number {
-                        n: key
-                    }
-                )
+    /// Leading sign and digits of the field in the plain number format, including a fraction for
+    /// field types that have one
+    fn numeric_prefix(field: &str, field_def: &Field) -> String {
+        let (field, has_fraction) = match field_def.field_type() {
+            FieldType::Number | FieldType::Decimal => {
+                (field_def.number_format().normalize_prefix(field), true)
+            }
+            FieldType::Integer | FieldType::UnsignedInteger | FieldType::Integer128
+            | FieldType::UnsignedInteger128 | FieldType::BigInteger => {
+                (field_def.number_format().normalize_prefix(field), false)
+            }
+            FieldType::HumanSize => { (Cow::Borrowed(field.trim_start()), true) }
+            _ => { (Cow::Borrowed(field.trim_start()), false) }
+        };
+        let mut end = 0;
+        let mut seen_separator = false;
+        for (i, c) in field.char_indices() {
+            if c.is_ascii_digit() {
+                end = i + 1;
+            } else if i == 0 && (c == '-' || c == '+') {
+                end = 1;
+            } else if c == '.' && has_fraction && !seen_separator {
+                seen_separator = true;
+            } else {
+                break;
             }
         }
+        field[..end].to_string()
     }
 
-    fn as_str(&self) -> Option<&str> {
-        match self {
-            Key::String { s } => { Some(s.as_str()) }
-            Key::Integer { .. } => {
-                None
+    fn is_null_token(field: &str, field_def: &Field) -> bool {
+        if field_def.null_tokens().is_empty() {
+            return false;
+        }
+        let field = match field_def.field_type() {
+            FieldType::String => { Self::trim_blanks(field, field_def) }
+            _ => { field.trim() }
+        };
+        field_def.null_tokens().iter().any(|token| token == field)
+    }
+
+    fn random(field_type: &FieldType) -> Key {
+        match field_type {
+            FieldType::Integer => {
+                Key::Integer {
+                    i: rand::random::<i64>()
+                }
             }
-            Key::Number { .. } => {
-                None
+            FieldType::Number => {
+                Key::Number {
+                    n: rand::random::<f64>()
+                }
             }
+            _ => {
+                Key::String {
+                    s: HEXLOWER.encode(&rand::random::<[u8; 16]>())
+                }
+            }
+        }
+    }
+
+    fn trim_blanks<'a>(field: &'a str, field_def: &Field) -> &'a str {
+        let is_blank = |c: char| field_def.blanks().is_blank(c);
+        let mut field = field;
+        if field_def.ignore_blanks() || field_def.ignore_leading_blanks() {
+            field = field.trim_start_matches(is_blank);
+        }
+        if field_def.ignore_blanks() || field_def.ignore_trailing_blanks() {
+            field = field.trim_end_matches(is_blank);
         }
+        field
     }
 
-    fn as_integer(&self) -> Option<i64> {
-        match self {
-            Key::String { .. } => {
-                None
+    fn string_value(field: &str, field_def: &Field) -> String {
+        let mut key = Self::trim_blanks(field, field_def).to_string();
+
+        if field_def.ignore_case() {
+            key = key.to_uppercase()
+        }
+        key
+    }
+
+    /// String value with the text options of the field applied
+    fn text_value(field: &str, field_def: &Field) -> String {
+        let mut key = Self::string_value(field, field_def);
+        if field_def.dictionary_order() {
+            key.retain(|c| c.is_alphanumeric() || c.is_whitespace());
+        }
+        if field_def.ignore_nonprinting() {
+            key.retain(|c| !Self::is_nonprinting(c));
+        }
+        if let Some(ignore_chars) = field_def.ignore_chars() {
+            key = ignore_chars.replace_all(&key, "").into_owned();
+        }
+        if field_def.ignore_accents() {
+            key = key.nfd().filter(|c| !is_combining_mark(*c)).collect();
+        }
+        if field_def.case_folding() {
+            key = caseless::default_case_fold_str(&key);
+        }
+        if let Some(normalization) = field_def.normalization() {
+            key = normalization.normalize(&key);
+        }
+        key
+    }
+
+    fn is_nonprinting(c: char) -> bool {
+        c.is_control() || matches!(
+            c,
+            '\u{ad}' | '\u{200b}'..='\u{200f}' | '\u{202a}'..='\u{202e}' | '\u{2060}'..='\u{2064}' | '\u{feff}'
+        )
+    }
+
+    /// Position of the name starting at 1 matched by the first three letters of the field, as
+    /// GNU `sort -M` does, or 0 if the field does not match any of the names
+    fn name_rank(field: &str, names: &[&str]) -> usize {
+        let prefix: String = field.trim_start().chars().take(3).flat_map(char::to_lowercase).collect();
+        if prefix.chars().count() < 3 {
+            return 0;
+        }
+        names.iter()
+            .position(|name| name.starts_with(prefix.as_str()))
+            .map(|position| position + 1)
+            .unwrap_or(0)
+    }
+
+    fn parse_ip_network(field: &str) -> Result<(IpAddr, u8), anyhow::Error> {
+        let (address, prefix) = match field.split_once('/') {
+            Some((address, prefix)) => { (IpAddr::from_str(address)?, Some(u8::from_str(prefix)?)) }
+            None => { (IpAddr::from_str(field)?, None) }
+        };
+        match address {
+            IpAddr::V4(v4) => {
+                let prefix = prefix.unwrap_or(32);
+                if prefix > 32 {
+                    return Err(anyhow!("Invalid prefix length: {}", field));
+                }
+                let mask = u32::MAX.checked_shl(32 - prefix as u32).unwrap_or(0);
+                Ok((IpAddr::V4(Ipv4Addr::from(u32::from(v4) & mask)), prefix))
             }
-            Key::Integer { i } => {
-                Some(*i)
+            IpAddr::V6(v6) => {
+                let prefix = prefix.unwrap_or(128);
+                if prefix > 128 {
+                    return Err(anyhow!("Invalid prefix length: {}", field));
+                }
+                let mask = u128::MAX.checked_shl(128 - prefix as u32).unwrap_or(0);
+                Ok((IpAddr::V6(Ipv6Addr::from(u128::from(v6) & mask)), prefix))
             }
-            Key::Number { .. } => {
-                None
+        }
+    }
+
+    fn parse_hex(field: &str) -> Result<String, anyhow::Error> {
+        let digits = field.strip_prefix("0x")
+            .or_else(|| field.strip_prefix("0X"))
+            .unwrap_or(field);
+        if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(anyhow!("Failed to parse hexadecimal integer: {}", field));
+        }
+        Ok(digits.to_ascii_lowercase())
+    }
+
+    fn parse_human_size(field: &str) -> Result<f64, anyhow::Error> {
+        let mut number_end = field
+            .find(|c: char| !(c.is_ascii_digit() || c == '.' || c == '-' || c == '+'))
+            .unwrap_or(field.len());
+        // an exponent, unlike the E suffix, is followed by digits
+        if let Some(exponent) = field[number_end..].strip_prefix(&['e', 'E'][..]) {
+            let digits = exponent.strip_prefix(&['-', '+'][..]).unwrap_or(exponent);
+            let digits_len = digits.find(|c: char| !c.is_ascii_digit()).unwrap_or(digits.len());
+            if digits_len > 0 {
+                number_end = field.len() - digits.len() + digits_len;
             }
         }
+        let number = f64::from_str(&field[..number_end])
+            .map_err(|e| anyhow!("Failed to parse size: {}, error: {}", field, e))?;
+        let suffix = field[number_end..].trim_start().to_ascii_uppercase();
+        let (prefix, base) = match suffix.strip_suffix("IB").or_else(|| suffix.strip_suffix('I')) {
+            Some(prefix) if !prefix.is_empty() => { (prefix, 1024_f64) }
+            _ => {
+                match suffix.strip_suffix('B') {
+                    Some(prefix) => { (prefix, 1000_f64) }
+                    None => { (suffix.as_str(), 1024_f64) }
+                }
+            }
+        };
+        let exponent = match prefix {
+            "" => { 0 }
+            "K" => { 1 }
+            "M" => { 2 }
+            "G" => { 3 }
+            "T" => { 4 }
+            "P" => { 5 }
+            "E" => { 6 }
+            "Z" => { 7 }
+            "Y" => { 8 }
+            "R" => { 9 }
+            "Q" => { 10 }
+            _ => {
+                return Err(anyhow!("Failed to parse size: {}, unknown suffix", field));
+            }
+        };
+        let size = number * base.powi(exponent);
+        // -0.0 and 0.0 are equal sizes
+        Ok(if size == 0.0 { 0.0 } else { size })
+    }
+
+    /// Numeric value in the plain format according to the [NumberFormat] of the field
+    fn number_value<'a>(field: &'a str, number_format: &NumberFormat) -> Result<Cow<'a, str>, anyhow::Error> {
+        number_format.normalize(field)
+    }
+
+    fn number_cmp(n: f64, o: f64) -> Ordering {
+        if n.is_nan() && o.is_nan() {
+            Ordering::Equal
+        } else if !n.is_nan() && o.is_nan() {
+            Ordering::Greater
+        } else if n.is_nan() && !o.is_nan() {
+            Ordering::Less
+        } else {
+            n.total_cmp(&o)
+        }
     }
 
-    fn as_number(&self) -> Option<f64> {
-        match self {
-            Key::String { .. } => {
-                None
+    fn from_value(field: &str, field_def: &Field) -> Result<Key, anyhow::Error> {
+        Self::from_formatted_value(field, field_def, field_def.number_format())
+    }
+
+    /// Parse the value with numbers in the `number_format`
+    fn from_formatted_value(field: &str, field_def: &Field, number_format: &NumberFormat) -> Result<Key, anyhow::Error> {
+        match field_def.field_type() {
+            FieldType::String => {
+                #[cfg(feature = "collation")]
+                if let Some(collation) = field_def.collation() {
+                    return Ok(
+                        Key::Collated {
+                            s: Self::text_value(field, field_def),
+                            collation: Arc::clone(collation),
+                        }
+                    );
+                }
+                Ok(
+                    Key::String {
+                        s: Self::text_value(field, field_def)
+                    }
+                )
+            }
+            FieldType::Integer => {
+                let key = i64::from_str(Self::number_value(field, number_format)?.as_ref())?;
+                Ok(
+                    Key::Integer {
+                        i: key
+                    }
+                )
+            }
+            FieldType::Number => {
+                let key = f64::from_str(Self::number_value(field, number_format)?.as_ref())?;
+                match field_def.nan() {
+                    Some(nan) if key.is_nan() => {
+                        Ok(
+                            Key::Nan {
+                                first: matches!(nan, Nan::First)
+                            }
+                        )
+                    }
+                    _ => {
+                        Ok(
+                            Key::Number {
+                                // -0.0 and 0.0 are distinct for total order comparison
+                                n: if key == 0.0 && !field_def.signed_zero() { 0.0 } else { key }
+                            }
+                        )
+                    }
+                }
+            }
+            FieldType::UnsignedInteger => {
+                let key = u64::from_str(Self::number_value(field, number_format)?.as_ref())?;
+                Ok(
+                    Key::UnsignedInteger {
+                        u: key as u128
+                    }
+                )
+            }
+            FieldType::Integer128 => {
+                let key = i128::from_str(Self::number_value(field, number_format)?.as_ref())?;
+                Ok(
+                    Key::Integer128 {
+                        i: key
+                    }
+                )
+            }
+            FieldType::UnsignedInteger128 => {
+                let key = u128::from_str(Self::number_value(field, number_format)?.as_ref())?;
+                Ok(
+                    Key::UnsignedInteger {
+                        u: key
+                    }
+                )
             }
-            Key::Integer { .. } => {
-                None
+            FieldType::HexInteger => {
+                Ok(
+                    Key::HexInteger {
+                        h: Self::parse_hex(field.trim())?
+                    }
+                )
             }
-            Key::Number { n } => {
-                Some(*n)
+            FieldType::DateTime { format, timezone } => {
+                Ok(
+                    Key::DateTime {
+                        t: format.parse(field, timezone)?
+                    }
+                )
+            }
+            FieldType::Version => {
+                Ok(
+                    Key::Version {
+                        s: Self::string_value(field, field_def)
+                    }
+                )
+            }
+            FieldType::Natural => {
+                Ok(
+                    Key::Natural {
+                        s: Self::string_value(field, field_def)
+                    }
+                )
+            }
+            FieldType::HumanSize => {
+                Ok(
+                    Key::HumanSize {
+                        n: Self::parse_human_size(field.trim())?
+                    }
+                )
+            }
+            FieldType::Ranked(values) => {
+                let key = Self::string_value(field, field_def);
+                let rank = values.iter()
+                    .position(|value| {
+                        if field_def.ignore_case() {
+                            value.chars().flat_map(char::to_uppercase).eq(key.chars())
+                        } else {
+                            value == &key
+                        }
+                    })
+                    .ok_or_else(|| anyhow!("Value not ranked: {}", field))?;
+                Ok(
+                    Key::Ranked {
+                        r: rank
+                    }
+                )
+            }
+            FieldType::Month => {
+                Ok(
+                    Key::Ranked {
+                        r: Self::name_rank(field, &MONTHS)
+                    }
+                )
+            }
+            FieldType::Weekday => {
+                Ok(
+                    Key::Ranked {
+                        r: Self::name_rank(field, &WEEKDAYS)
+                    }
+                )
+            }
+            FieldType::IpAddr => {
+                Ok(
+                    Key::IpAddr {
+                        a: IpAddr::from_str(field.trim())?
+                    }
+                )
+            }
+            FieldType::IpNetwork => {
+                let (network, prefix) = Self::parse_ip_network(field.trim())?;
+                Ok(
+                    Key::IpNetwork {
+                        network,
+                        prefix,
+                    }
+                )
+            }
+            FieldType::Custom(custom_type) => {
+                Ok(
+                    Key::Custom {
+                        k: custom_type.extract(Self::string_value(field, field_def).as_str())?
+                    }
+                )
+            }
+            FieldType::Spatial { .. } => {
+                Err(anyhow!("Spatial field must be created with Field::new_spatial"))
+            }
+            FieldType::BigInteger => {
+                Ok(
+                    Key::Decimal {
+                        d: Box::new(Decimal::parse(Self::number_value(field, number_format)?.as_ref(), '.', false)?)
+                    }
+                )
+            }
+            FieldType::Decimal => {
+                Ok(
+                    Key::Decimal {
+                        d: Box::new(Decimal::parse(Self::number_value(field, number_format)?.as_ref(), '.', true)?)
+                    }
+                )
             }
         }
     }
@@ -114,11 +641,7 @@ impl Eq for Key {}
 
 impl PartialEq<Self> for Key {
     fn eq(&self, other: &Self) -> bool {
-        match self {
-            Key::String { s } => { s.eq(other.as_str().unwrap()) }
-            Key::Integer { i } => { i.eq(&other.as_integer().unwrap()) }
-            Key::Number { n } => { n.eq(&other.as_number().unwrap()) }
-        }
+        self.cmp(other) == Ordering::Equal
     }
 }
 
@@ -130,20 +653,56 @@ impl PartialOrd<Self> for Key {
 
 impl Ord for Key {
     fn cmp(&self, other: &Self) -> Ordering {
-        match self {
-            Key::String { s } => { s.as_str().cmp(other.as_str().unwrap()) }
-            Key::Integer { i } => { i.cmp(&other.as_integer().unwrap()) }
-            Key::Number { n } => {
-                if n.is_nan() && other.as_number().unwrap().is_nan() {
-                    Ordering::Equal
-                } else if !n.is_nan() && other.as_number().unwrap().is_nan() {
+        match (self, other) {
+            (Key::Null { .. }, Key::Null { .. }) => { Ordering::Equal }
+            (Key::Null { nulls }, _) => {
+                match nulls {
+                    Some(Nulls::First) => { Ordering::Less }
+                    Some(Nulls::Last) | None => { Ordering::Greater }
+                }
+            }
+            (_, Key::Null { .. }) => { other.cmp(self).reverse() }
+            (Key::Invalid { s, .. }, Key::Invalid { s: o, .. }) => { s.cmp(o) }
+            (Key::Invalid { first, .. }, _) => {
+                if *first {
+                    Ordering::Less
+                } else {
                     Ordering::Greater
-                } else if n.is_nan() && !other.as_number().unwrap().is_nan() {
+                }
+            }
+            (_, Key::Invalid { .. }) => { other.cmp(self).reverse() }
+            (Key::Nan { .. }, Key::Nan { .. }) => { Ordering::Equal }
+            (Key::Nan { first }, _) => {
+                if *first {
                     Ordering::Less
                 } else {
-                    n.partial_cmp(&other.as_number().unwrap()).unwrap()
+                    Ordering::Greater
                 }
             }
+            (_, Key::Nan { .. }) => { other.cmp(self).reverse() }
+            (Key::String { s }, Key::String { s: o }) => { s.as_str().cmp(o.as_str()) }
+            #[cfg(feature = "collation")]
+            (Key::Collated { s, collation }, Key::Collated { s: o, .. }) => { collation.compare(s, o) }
+            (Key::Integer { i }, Key::Integer { i: o }) => { i.cmp(o) }
+            (Key::Number { n }, Key::Number { n: o }) => { Self::number_cmp(*n, *o) }
+            (Key::UnsignedInteger { u }, Key::UnsignedInteger { u: o }) => { u.cmp(o) }
+            (Key::Integer128 { i }, Key::Integer128 { i: o }) => { i.cmp(o) }
+            (Key::HexInteger { h }, Key::HexInteger { h: o }) => { digits_cmp(h.as_bytes(), o.as_bytes()) }
+            (Key::DateTime { t }, Key::DateTime { t: o }) => { t.cmp(o) }
+            (Key::Version { s }, Key::Version { s: o }) => { version_cmp(s, o) }
+            (Key::Natural { s }, Key::Natural { s: o }) => { natural_cmp(s, o) }
+            (Key::HumanSize { n }, Key::HumanSize { n: o }) => { Self::number_cmp(*n, *o) }
+            (Key::Decimal { d }, Key::Decimal { d: o }) => { d.cmp(o) }
+            (Key::Ranked { r }, Key::Ranked { r: o }) => { r.cmp(o) }
+            (Key::IpAddr { a }, Key::IpAddr { a: o }) => { a.cmp(o) }
+            (Key::Spatial { d }, Key::Spatial { d: o }) => { d.cmp(o) }
+            (Key::Custom { k }, Key::Custom { k: o }) => { k.any_cmp(o.as_ref()) }
+            (Key::IpNetwork { network, prefix }, Key::IpNetwork { network: o_network, prefix: o_prefix }) => {
+                network.cmp(o_network).then_with(|| prefix.cmp(o_prefix))
+            }
+            _ => {
+                panic!("Comparing keys of different types: {:?}, {:?}", self, other)
+            }
         }
     }
-}
\ No newline at end of file
+}
@@ -55,10 +55,27 @@ pub(crate) mod line_record;
 pub(crate) mod key;
 pub(crate) mod sorted_chunk_file;
 pub(crate) mod unmerged_chunk_file;
+pub(crate) mod record_reader;
 pub(crate) mod config;
 pub(crate) mod chunk_iterator;
+pub(crate) mod compare;
+pub(crate) mod decimal;
 
 pub mod sort;
 pub mod field;
 pub mod field_type;
+pub mod field_encoding;
+pub mod date_time_format;
 pub mod order;
+pub mod nulls;
+pub mod nan;
+pub mod parse_failure;
+pub mod spatial;
+pub mod custom_type;
+pub mod normalization;
+#[cfg(feature = "collation")]
+pub mod collation;
+pub mod blanks;
+pub mod number_format;
+pub mod unique;
+pub mod duplicates;
@@ -2,67 +2,159 @@ use std::cmp::Ordering;
 
 use anyhow::anyhow;
 
+use crate::config::Config;
 use crate::field::Field;
 use crate::key::Key;
 use crate::order::Order;
 
+/// Position of a line in the input, the index of the input file and the offset of the line in
+/// the file. Used to keep the input order of records with equal keys.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub(crate) struct Position {
+    file_index: usize,
+    offset: u64,
+}
+
+impl Position {
+    pub(crate) fn new(file_index: usize, offset: u64) -> Position {
+        Position {
+            file_index,
+            offset,
+        }
+    }
+
+    pub(crate) fn file_index(&self) -> usize {
+        self.file_index
+    }
+
+    /// Prefix of a line in an intermediate file
+    pub(crate) fn prefix(&self) -> String {
+        format!("{} {} ", self.file_index, self.offset)
+    }
+
+    /// Parse the prefix of a line in an intermediate file, returning the position and the length
+    /// of the prefix
+    pub(crate) fn parse(line: &str) -> Result<(Position, usize), anyhow::Error> {
+        let mut parts = line.splitn(3, ' ');
+        match (parts.next(), parts.next(), parts.next()) {
+            (Some(file_index), Some(offset), Some(_)) => {
+                Ok(
+                    (
+                        Position::new(file_index.parse()?, offset.parse()?),
+                        file_index.len() + offset.len() + 2,
+                    )
+                )
+            }
+            _ => {
+                Err(anyhow!("Missing line position: {}", line))
+            }
+        }
+    }
+}
+
 #[derive(Debug)]
 pub(crate) struct LineRecord {
     line: String,
     keys: Vec<Key>,
-    order: Order,
+    // boxed to keep the records small when the input order is not kept
+    position: Option<Box<Position>>,
 }
 
 impl LineRecord {
-    pub(crate) fn new(line: String, fields: &Vec<Field>, field_separator: char, order: Order) -> Result<LineRecord, anyhow::Error> {
-        if fields.len() == 1 && fields[0].index() == 0 {
+    pub(crate) fn new(line: String, fields: &Vec<Field>, field_separator: char) -> Result<LineRecord, anyhow::Error> {
+        // the line terminator is not a part of the keys
+        let record = Self::strip_terminator(line.as_str());
+        if fields.len() == 1 && fields[0].index() == 0 && fields[0].components().is_empty()
+            && fields[0].compute().is_none() {
             let field = &fields[0];
-            let key = Key::new(line.as_str(), field)
+            let key = Key::new(record, field)
                 .map_err(|e| anyhow!("line: {line}, error: {e}"))?;
             Ok(
                 LineRecord {
                     line,
                     keys: vec![key],
-                    order,
+                    position: None,
                 }
             )
         } else {
             let mut keys: Vec<Key> = Vec::new();
-            let parts: Vec<&str> = line.split(field_separator).collect();
-            let mut error = None;
+            let parts: Vec<&str> = record.split(field_separator).collect();
             for field in fields {
-                if field.index() == 0 {
-                    error = Some(
-                        anyhow!(
-                            "Field index of 0 must be specified only once, meaning the entire line is to be used as a key".to_string()
-                        )
-                    );
-                    break;
+                match Self::key(&parts, field, field_separator) {
+                    Ok(key) => {
+                        keys.push(key)
+                    }
+                    Err(e) => {
+                        return Err(anyhow!("line: {line}, error: {e}"));
+                    }
                 }
-                if field.index() > parts.len() {
-                    error = Some(
-                        anyhow!(
-                            "Requested comparison for field {} but there are only {} fields using {} as field separator.",
-                            field.index(),
-                            parts.len(),
-                            field_separator,
-                       )
-                    );
-                    break;
+            }
+            Ok(
+                LineRecord {
+                    line,
+                    keys,
+                    position: None,
                 }
-                keys.push(Key::new(parts[field.index() - 1], field)?)
+            )
+        }
+    }
+
+    /// Create a line record according to the fields of the config
+    pub(crate) fn from_config(line: String, config: &Config) -> Result<LineRecord, anyhow::Error> {
+        Self::new(line, config.fields(), config.field_separator())
+    }
+
+    pub(crate) fn with_position(mut self, position: Position) -> LineRecord {
+        self.position = Some(Box::new(position));
+        self
+    }
+
+    pub(crate) fn position(&self) -> Option<&Position> {
+        self.position.as_deref()
+    }
+
+    /// The line without the line terminator
+    pub(crate) fn text(&self) -> &str {
+        Self::strip_terminator(self.line.as_str())
+    }
+
+    fn strip_terminator(line: &str) -> &str {
+        let line = line.strip_suffix('\n').unwrap_or(line);
+        line.strip_suffix('\r').unwrap_or(line)
+    }
+
+    fn key(parts: &[&str], field: &Field, field_separator: char) -> Result<Key, anyhow::Error> {
+        if let Some(compute) = field.compute() {
+            Key::new(compute.value(parts)?.as_str(), field)
+        } else if field.components().is_empty() {
+            Key::new(Self::part(parts, field, field_separator)?, field)
+        } else {
+            let mut components = Vec::with_capacity(field.components().len());
+            for component in field.components() {
+                components.push(Key::new(Self::part(parts, component, field_separator)?, component)?);
             }
-            if let Some(e) = error {
-                Err(anyhow!("line: {line}, error: {e}"))
-            } else {
-                Ok(
-                    LineRecord {
-                        line,
-                        keys,
-                        order,
-                    }
+            Key::from_components(components, field)
+        }
+    }
+
+    fn part<'a>(parts: &[&'a str], field: &Field, field_separator: char) -> Result<&'a str, anyhow::Error> {
+        if field.index() == 0 {
+            Err(
+                anyhow!(
+                    "Field index of 0 must be specified only once, meaning the entire line is to be used as a key".to_string()
                 )
-            }
+            )
+        } else if field.index() > parts.len() {
+            Err(
+                anyhow!(
+                    "Requested comparison for field {} but there are only {} fields using {} as field separator.",
+                    field.index(),
+                    parts.len(),
+                    field_separator,
+                )
+            )
+        } else {
+            Ok(parts[field.index() - 1])
         }
     }
 
@@ -71,48 +163,44 @@ impl LineRecord {
     }
 }
 
-impl Eq for LineRecord {}
-
-impl PartialEq<Self> for LineRecord {
-    fn eq(&self, other: &Self) -> bool {
-        self.keys == other.keys
-    }
-}
-
-impl PartialOrd<Self> for LineRecord {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
+impl LineRecord {
+    /// Compare the keys only, without breaking ties
+    pub(crate) fn keys_cmp(&self, other: &Self, config: &Config) -> Ordering {
+        for ((key, other_key), order) in self.keys.iter().zip(other.keys.iter()).zip(config.orders()) {
+            let ordering = key.order_cmp(other_key, order);
+            if ordering != Ordering::Equal {
+                return ordering;
+            }
+        }
+        self.keys.len().cmp(&other.keys.len())
     }
-}
 
-impl Ord for LineRecord {
-    fn cmp(&self, other: &Self) -> Ordering {
-        let ordering = self.keys.cmp(&other.keys);
-        match ordering {
-            Ordering::Less => {
-                match &self.order {
+    /// Compare the keys and break the ties of equal keys
+    pub(crate) fn compare(&self, other: &Self, config: &Config) -> Ordering {
+        let ordering = self.keys_cmp(other, config);
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+        // the whole line comparison of records with equal keys
+        if config.last_resort() {
+            let ordering = self.text().cmp(other.text());
+            if ordering != Ordering::Equal {
+                return match config.order() {
                     Order::Asc => {
-                        Ordering::Less
+                        ordering
                     }
                     Order::Desc => {
-                        Ordering::Greater
+                        ordering.reverse()
                     }
-                }
+                };
             }
-            Ordering::Equal => {
-                Ordering::Equal
-            }
-            Ordering::Greater => {
-                match &self.order {
-                    Order::Asc => {
-                        Ordering::Greater
-                    }
-                    Order::Desc => {
-                        Ordering::Less
-                    }
-                }
+        }
+        if let (Some(position), Some(other_position)) = (&self.position, &other.position) {
+            let ordering = position.cmp(other_position);
+            if ordering != Ordering::Equal {
+                return ordering;
             }
         }
+        Ordering::Equal
     }
 }
-
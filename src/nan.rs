@@ -0,0 +1,12 @@
+/// Position of NaN values of [FieldType::Number](crate::field_type::FieldType::Number) fields in
+/// the sorted output
+///
+/// The position does not depend on the [Order](crate::order::Order). By default NaN values are
+/// less than any other number, that is first in ascending and last in descending order.
+#[derive(Clone, Debug)]
+pub enum Nan {
+    /// NaN values precede all other values
+    First,
+    /// NaN values follow all other values
+    Last,
+}
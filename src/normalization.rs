@@ -0,0 +1,42 @@
+use unicode_normalization::UnicodeNormalization;
+
+/// Unicode normalization form applied to [FieldType::String](crate::field_type::FieldType::String)
+/// keys, so that canonically or compatibly equivalent strings compare as equal. For example `é`
+/// written as one precomposed character and as `e` followed by a combining accent.
+#[derive(Clone, Debug)]
+pub enum Normalization {
+    /// Canonical decomposition followed by canonical composition
+    Nfc,
+    /// Canonical decomposition
+    Nfd,
+    /// Compatibility decomposition followed by canonical composition, for example `ﬁ` becomes `fi`
+    Nfkc,
+    /// Compatibility decomposition
+    Nfkd,
+}
+
+impl Normalization {
+    pub(crate) fn normalize(&self, value: &str) -> String {
+        match self {
+            Normalization::Nfc => { value.nfc().collect() }
+            Normalization::Nfd => { value.nfd().collect() }
+            Normalization::Nfkc => { value.nfkc().collect() }
+            Normalization::Nfkd => { value.nfkd().collect() }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::normalization::Normalization;
+
+    #[test]
+    fn test_normalize() {
+        let precomposed = "caf\u{e9}";
+        let decomposed = "cafe\u{301}";
+        assert_eq!(Normalization::Nfc.normalize(decomposed), precomposed);
+        assert_eq!(Normalization::Nfd.normalize(precomposed), decomposed);
+        assert_eq!(Normalization::Nfkc.normalize("\u{fb01}le"), "file");
+        assert_eq!(Normalization::Nfkd.normalize("\u{fb01}l\u{e9}"), "file\u{301}");
+    }
+}
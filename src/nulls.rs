@@ -0,0 +1,11 @@
+/// Position of NULL values in the sorted output
+///
+/// The position does not depend on the [Order](crate::order::Order), matching SQL `NULLS FIRST`
+/// and `NULLS LAST`.
+#[derive(Clone, Debug)]
+pub enum Nulls {
+    /// NULL values precede all other values
+    First,
+    /// NULL values follow all other values
+    Last,
+}
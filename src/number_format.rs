@@ -0,0 +1,193 @@
+use std::borrow::Cow;
+
+use anyhow::anyhow;
+
+/// Format of numeric field values, for example `1.234,56` in European exports or `($1,234.50)`
+/// in accounting reports
+///
+/// Values are converted to the plain format, with `.` as the decimal separator and without
+/// grouping separators and currency symbols, before they are parsed according to the
+/// [FieldType](crate::field_type::FieldType) of the field. Grouping separators are removed
+/// wherever they appear. A `.` that is neither the decimal nor the grouping separator is
+/// rejected, so `1.234` is not read as 1.234 when the decimal separator is `,`.
+///
+/// # Examples
+/// ```
+/// use text_file_sort::field::Field;
+/// use text_file_sort::field_type::FieldType;
+/// use text_file_sort::number_format::NumberFormat;
+/// // 1.234,56 €
+/// let field = Field::new(3, FieldType::Number)
+///     .with_number_format(
+///         NumberFormat::new()
+///             .with_decimal_separator(',')
+///             .with_grouping_separator('.')
+///             .with_currency_symbols(vec!["€".to_string(), "EUR".to_string()])
+///     );
+/// ```
+#[derive(Clone, Debug)]
+pub struct NumberFormat {
+    decimal_separator: char,
+    grouping_separator: Option<char>,
+    currency_symbols: Vec<String>,
+    parenthesized_negatives: bool,
+}
+
+impl NumberFormat {
+    /// Create the plain [NumberFormat], with `.` as the decimal separator and without grouping
+    /// separators
+    pub fn new() -> NumberFormat {
+        NumberFormat {
+            decimal_separator: '.',
+            grouping_separator: None,
+            currency_symbols: vec![],
+            parenthesized_negatives: false,
+        }
+    }
+
+    /// Get the decimal separator
+    pub fn decimal_separator(&self) -> char {
+        self.decimal_separator
+    }
+
+    /// Get the grouping separator
+    pub fn grouping_separator(&self) -> Option<char> {
+        self.grouping_separator
+    }
+
+    /// Get the currency symbols
+    pub fn currency_symbols(&self) -> &Vec<String> {
+        &self.currency_symbols
+    }
+
+    /// Get the parenthesized negatives setting
+    pub fn parenthesized_negatives(&self) -> bool {
+        self.parenthesized_negatives
+    }
+
+    /// Specify the decimal separator. The default is '.'
+    pub fn with_decimal_separator(mut self, decimal_separator: char) -> NumberFormat {
+        self.decimal_separator = decimal_separator;
+        self
+    }
+
+    /// Specify the grouping (thousands) separator, for example ',', '.', ' ' or '\''
+    pub fn with_grouping_separator(mut self, grouping_separator: char) -> NumberFormat {
+        self.grouping_separator = Some(grouping_separator);
+        self
+    }
+
+    /// Specify currency symbols to strip, for example `$`, `€` or `USD`
+    pub fn with_currency_symbols(mut self, currency_symbols: Vec<String>) -> NumberFormat {
+        self.currency_symbols = currency_symbols;
+        self
+    }
+
+    /// Specify whether a value in parentheses, for example `(1,234.50)`, is negative
+    pub fn with_parenthesized_negatives(mut self, parenthesized_negatives: bool) -> NumberFormat {
+        self.parenthesized_negatives = parenthesized_negatives;
+        self
+    }
+
+    fn is_plain(&self) -> bool {
+        self.decimal_separator == '.'
+            && self.grouping_separator.is_none()
+            && self.currency_symbols.is_empty()
+            && !self.parenthesized_negatives
+    }
+
+    /// Convert the value to the plain format
+    pub(crate) fn normalize<'a>(&self, value: &'a str) -> Result<Cow<'a, str>, anyhow::Error> {
+        match self.convert(value) {
+            (normalized, true) => { Ok(normalized) }
+            (_, false) => { Err(anyhow!("Unexpected '.' in number: {}, format: {:?}", value, self)) }
+        }
+    }
+
+    /// Convert the value to the plain format up to the first '.' that is neither the decimal nor
+    /// the grouping separator
+    pub(crate) fn normalize_prefix<'a>(&self, value: &'a str) -> Cow<'a, str> {
+        self.convert(value).0
+    }
+
+    /// Convert the value to the plain format, returning false if the conversion stopped at a '.'
+    /// that is not a separator of the format
+    fn convert<'a>(&self, value: &'a str) -> (Cow<'a, str>, bool) {
+        let value = value.trim();
+        if self.is_plain() {
+            return (Cow::Borrowed(value), true);
+        }
+        let (negative, value) = match value.strip_prefix('(').and_then(|value| value.strip_suffix(')')) {
+            Some(inner) if self.parenthesized_negatives => { (true, inner) }
+            _ => { (false, value) }
+        };
+        let mut value = value.to_string();
+        for currency_symbol in &self.currency_symbols {
+            value = value.replace(currency_symbol.as_str(), "");
+        }
+        let mut normalized = String::with_capacity(value.len() + 1);
+        if negative {
+            normalized.push('-');
+        }
+        for c in value.trim().chars() {
+            if Some(c) == self.grouping_separator {
+                continue;
+            } else if c == self.decimal_separator {
+                normalized.push('.');
+            } else if c == '.' {
+                return (Cow::Owned(normalized), false);
+            } else if !c.is_whitespace() {
+                normalized.push(c);
+            }
+        }
+        (Cow::Owned(normalized), true)
+    }
+}
+
+impl Default for NumberFormat {
+    fn default() -> Self {
+        NumberFormat::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::number_format::NumberFormat;
+
+    #[test]
+    fn test_normalize() {
+        let plain = NumberFormat::new();
+        assert_eq!(plain.normalize(" 1,234.5 ").unwrap(), "1,234.5");
+
+        let european = NumberFormat::new()
+            .with_decimal_separator(',')
+            .with_grouping_separator('.')
+            .with_currency_symbols(vec!["€".to_string()]);
+        assert_eq!(european.normalize("1.234,56").unwrap(), "1234.56");
+        assert_eq!(european.normalize("-1.234.567,8 €").unwrap(), "-1234567.8");
+        assert_eq!(european.normalize("€ 0,5").unwrap(), "0.5");
+
+        let accounting = NumberFormat::new()
+            .with_grouping_separator(',')
+            .with_currency_symbols(vec!["$".to_string(), "USD".to_string()])
+            .with_parenthesized_negatives(true);
+        assert_eq!(accounting.normalize("($1,234.50)").unwrap(), "-1234.50");
+        assert_eq!(accounting.normalize("1,000 USD").unwrap(), "1000");
+        assert_eq!(accounting.normalize("-$7").unwrap(), "-7");
+
+        let swiss = NumberFormat::new().with_grouping_separator('\'');
+        assert_eq!(swiss.normalize("1'234'567.25").unwrap(), "1234567.25");
+
+        let french = NumberFormat::new()
+            .with_decimal_separator(',')
+            .with_grouping_separator('\u{202f}');
+        assert_eq!(french.normalize("1\u{202f}234,5").unwrap(), "1234.5");
+
+        // '.' is neither the decimal nor the grouping separator
+        let decimal_comma = NumberFormat::new().with_decimal_separator(',');
+        assert_eq!(decimal_comma.normalize("1,5").unwrap(), "1.5");
+        assert!(decimal_comma.normalize("1.234").is_err());
+        assert_eq!(decimal_comma.normalize_prefix("12.5"), "12");
+        assert_eq!(european.normalize_prefix("1.234,5x"), "1234.5x");
+    }
+}
@@ -0,0 +1,29 @@
+/// Policy for field values that fail to parse according to the [FieldType](crate::field_type::FieldType)
+/// of the field
+///
+/// The policy applies the same way to sort, merge and check.
+#[derive(Clone, Debug)]
+pub enum ParseFailure {
+    /// Fail the operation
+    Fail,
+    /// Values that fail to parse precede all other values. They are compared as strings among
+    /// themselves and their position does not depend on the [Order](crate::order::Order).
+    First,
+    /// Values that fail to parse follow all other values, for example text in a mixed type
+    /// column. They are compared as strings among themselves and their position does not depend
+    /// on the [Order](crate::order::Order).
+    Last,
+    /// Values that fail to parse are compared as NULL, see
+    /// [Field::with_nulls](crate::field::Field::with_nulls)
+    Null,
+    /// Values that fail to parse are compared as zero. Applies to numeric field types, for other
+    /// field types the operation fails.
+    Zero,
+    /// The leading numeric prefix of the value is compared, or zero if there is none, similar to
+    /// GNU `sort -n`. For example `12abc` is compared as `12` and `1.5x` as `1.5`. The prefix is
+    /// taken after the value is converted according to the
+    /// [NumberFormat](crate::number_format::NumberFormat) of the field, so `1.234,5x` is compared
+    /// as `1234.5` in a European format. Applies to numeric field types, for other field types
+    /// the operation fails.
+    Prefix,
+}
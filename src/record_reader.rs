@@ -0,0 +1,48 @@
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+
+use crate::config::Config;
+use crate::line_record::LineRecord;
+
+/// Reader of the records of an input file, skipping the ignored lines
+pub(crate) struct RecordReader {
+    path: PathBuf,
+    reader: BufReader<File>,
+    line_number: usize,
+}
+
+impl RecordReader {
+    pub(crate) fn new(path: &Path) -> Result<RecordReader, anyhow::Error> {
+        let file = File::open(path).with_context(|| format!("path: {}", path.display()))?;
+        Ok(
+            RecordReader {
+                path: path.to_path_buf(),
+                reader: BufReader::new(file),
+                line_number: 0,
+            }
+        )
+    }
+
+    pub(crate) fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Read the next record that is not ignored, with its line number starting at 1
+    pub(crate) fn next(&mut self, config: &Config) -> Result<Option<(usize, LineRecord)>, anyhow::Error> {
+        let mut line = String::new();
+        while self.reader.read_line(&mut line)? != 0 {
+            self.line_number += 1;
+            if config.ignored(&line) {
+                line.clear();
+                continue;
+            }
+            let line_record = LineRecord::from_config(line, config)
+                .with_context(|| format!("path: {}, line: {}", self.path.display(), self.line_number))?;
+            return Ok(Some((self.line_number, line_record)));
+        }
+        Ok(None)
+    }
+}
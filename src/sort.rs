@@ -1,5 +1,5 @@
 use std::cell::RefCell;
-use std::cmp::{max, Reverse};
+use std::cmp::{max, Ordering, Reverse};
 use std::collections::BinaryHeap;
 use std::fs::File;
 use std::io::{BufRead, BufReader, BufWriter, Write};
@@ -18,19 +18,23 @@ use tempfile::{Builder, NamedTempFile};
 
 use crate::chunk_iterator::ChunkIterator;
 use crate::config::Config;
+use crate::duplicates;
+use crate::duplicates::DuplicateLine;
 use crate::field::Field;
 use crate::field_type::FieldType;
 use crate::line_record::LineRecord;
 use crate::order::Order;
+use crate::record_reader::RecordReader;
 use crate::sort_command::SortCommand;
 use crate::sorted_chunk_file::SortedChunkFile;
-use crate::unmerged_chunk_file::UnmergedChunkFile;
+use crate::unique::{Tombstone, Unique, UniqueFilter};
+use crate::unmerged_chunk_file::{Positions, UnmergedChunkFile};
 
 thread_local! {
     pub(crate) static LINE_CAPACITY: RefCell<usize> = const { RefCell::new(1) };
     pub(crate) static LINE_RECORDS_CAPACITY: RefCell<usize> = const { RefCell::new(1) };
     pub(crate) static SORTED_FILES: RefCell<BinaryHeap<Reverse<SortedChunkFile>>> = const { RefCell::new(BinaryHeap::new()) };
-    pub(crate) static CONFIG: RefCell<Option<Config>> = const { RefCell::new(None) };
+    pub(crate) static CONFIG: RefCell<Option<Arc<Config>>> = const { RefCell::new(None) };
 }
 
 pub(crate) fn get_line_capacity() -> usize {
@@ -49,7 +53,7 @@ pub(crate) fn set_line_records_capacity(value: usize) {
     LINE_RECORDS_CAPACITY.with(|capacity| capacity.replace(value));
 }
 
-pub(crate) fn get_tl_config() -> Config {
+pub(crate) fn get_tl_config() -> Arc<Config> {
     CONFIG.with(
         |config| {
             config.borrow().as_ref().unwrap().clone()
@@ -103,6 +107,11 @@ pub struct Sort {
     prefix: Vec<String>,
     suffix: Vec<String>,
     endl: char,
+    stable: bool,
+    last_resort: bool,
+    unique: Option<Unique>,
+    priorities: Option<Vec<usize>>,
+    tombstone: Option<Tombstone>,
 }
 
 impl Sort {
@@ -119,6 +128,7 @@ impl Sort {
     /// * default Order is Asc
     /// * prefix and suffix are empty
     /// * default end lines is '\n'
+    /// * the order of records with equal keys is not defined
     ///
     /// The Sort implementation will increase the file descriptor rlimit to accommodate configured
     /// open files
@@ -139,6 +149,11 @@ impl Sort {
             prefix: vec![],
             suffix: vec![],
             endl: '\n',
+            stable: false,
+            last_resort: false,
+            unique: None,
+            priorities: None,
+            tombstone: None,
         }
     }
 
@@ -231,6 +246,49 @@ impl Sort {
         self.endl = endl
     }
 
+    /// Keep the input order of records with equal keys, first by the order of the input files and
+    /// then by the order of the lines in each file. The output does not depend on the number of
+    /// tasks and the chunk size. The default is false
+    pub fn with_stable(&mut self, stable: bool) {
+        self.stable = stable
+    }
+
+    /// Compare records with equal keys by the complete line, similar to the last resort comparison
+    /// of GNU sort. The comparison follows the [Order] of the sort and precedes the input order of
+    /// a stable sort. The default is false
+    pub fn with_last_resort(&mut self, last_resort: bool) {
+        self.last_resort = last_resort
+    }
+
+    /// Output only one of the records with equal keys, similar to GNU `sort -u`. See [Unique] for
+    /// which record is kept. Records are compared by the keys of the fields only. Duplicates are
+    /// removed while sorting chunks and while merging, so they are not written to intermediate
+    /// files. Also applies to merge, and check requires the keys to be strictly increasing.
+    pub fn with_unique(&mut self, unique: Unique) {
+        self.unique = Some(unique)
+    }
+
+    /// Replace records with equal keys by the record from the input file with the highest
+    /// priority, for example to merge an OSM change file into a sorted base dump. `priorities`
+    /// holds the priority of each input file by its index, missing priorities are 0. Of the
+    /// records with equal keys from inputs of the same priority the last one in the input order
+    /// is kept. Takes precedence over [Sort::with_unique]. Check accepts equal keys in an input
+    /// file unless [Sort::with_unique] is also set.
+    pub fn with_priorities(&mut self, priorities: Vec<usize>) {
+        self.priorities = Some(priorities)
+    }
+
+    /// Drop the records whose line, without the line terminator, matches the `tombstone`
+    /// predicate. Records are dropped only when the output is written, after records with equal
+    /// keys were replaced according to [Sort::with_priorities], so a tombstone in a change file
+    /// deletes the record of the base dump.
+    pub fn with_tombstone<F>(&mut self, tombstone: F)
+    where
+        F: Fn(&str) -> bool + Send + Sync + 'static,
+    {
+        self.tombstone = Some(Tombstone::new(tombstone))
+    }
+
     /// Sort input files or STDIN
     pub fn sort(&self) -> Result<(), anyhow::Error> {
         let config = self.create_config();
@@ -287,7 +345,12 @@ impl Sort {
             self.order.clone(),
             self.prefix.clone(),
             self.suffix.clone(),
-            self.endl
+            self.endl,
+            self.stable,
+            self.last_resort,
+            self.unique.clone(),
+            self.priorities.clone(),
+            self.tombstone.clone(),
         )
     }
 
@@ -359,44 +422,59 @@ impl Sort {
         Ok(result)
     }
 
-    pub(crate) fn internal_check(path: &PathBuf, config: &Config) -> Result<bool, anyhow::Error> {
-        let mut result = true;
-        let mut line = String::new();
-        let mut previous: Option<LineRecord> = None;
-        let mut reader = BufReader::new(File::open(path)?);
-        while reader.read_line(&mut line)? != 0 {
-            if config.ignore_empty() && line.trim().is_empty() {
-                continue;
-            }
+    /// Report every group of records with equal keys in sorted input files, for example to
+    /// validate that the primary key fields of a pg_dump table are unique. The input files are
+    /// merged, so groups may span several files. `report` is called with the lines of each group,
+    /// their source files and line numbers. Returns the number of groups reported, or an error if
+    /// the input is not sorted.
+    pub fn check_duplicates<F>(&self, report: F) -> Result<usize, anyhow::Error>
+    where
+        F: FnMut(&[DuplicateLine]) -> Result<(), anyhow::Error>,
+    {
+        let config = self.create_config();
+        duplicates::check_duplicates(&self.input_files, &config, report)
+    }
 
-            if let Some(r) = config.ignore_lines() {
-                if r.is_match(line.trim()) {
-                    continue;
+    /// Same as [Sort::check_duplicates] but write the report to a side file. Each line of the
+    /// report is `path:line_number:line` and groups are separated by an empty line.
+    pub fn report_duplicates(&self, report: &Path) -> Result<usize, anyhow::Error> {
+        let mut writer = BufWriter::new(
+            File::create(report).with_context(|| format!("path: {}", report.display()))?
+        );
+        let mut first = true;
+        let groups = self.check_duplicates(
+            |duplicates| {
+                if !first {
+                    writer.write_all(b"\n")?;
                 }
-            }
-            let current_line_record = LineRecord::new(
-                line,
-                config.fields(),
-                config.field_separator(),
-                config.order().clone(),
-            )?;
-
-            match previous {
-                None => {
-                    previous = Some(current_line_record);
+                first = false;
+                for duplicate in duplicates {
+                    writeln!(writer, "{}:{}:{}", duplicate.path().display(), duplicate.line_number(), duplicate.line())?;
                 }
-                Some(previous_line_record) => {
-                    if previous_line_record <= current_line_record {
-                        previous = Some(current_line_record);
-                    } else {
-                        result = false;
-                        break;
-                    }
+                Ok(())
+            }
+        )?;
+        writer.flush()?;
+        Ok(groups)
+    }
+
+    pub(crate) fn internal_check(path: &Path, config: &Config) -> Result<bool, anyhow::Error> {
+        let mut reader = RecordReader::new(path)?;
+        let mut previous: Option<LineRecord> = None;
+        while let Some((_line_number, current_line_record)) = reader.next(config)? {
+            if let Some(previous_line_record) = previous {
+                let sorted = if config.unique().is_some() {
+                    previous_line_record.keys_cmp(&current_line_record, config) == Ordering::Less
+                } else {
+                    previous_line_record.compare(&current_line_record, config) != Ordering::Greater
+                };
+                if !sorted {
+                    return Ok(false);
                 }
             }
-            line = String::new();
+            previous = Some(current_line_record);
         }
-        Ok(result)
+        Ok(true)
     }
 
     pub fn merge(&self) -> Result<(), anyhow::Error> {
@@ -414,20 +492,33 @@ impl Sort {
         Ok(())
     }
 
-    pub(crate) fn internal_merge(files: Vec<PathBuf>, config: &Config, remove_merged: bool, add_prefix_suffix: bool) -> Result<(PathBuf, usize), anyhow::Error> {
+    /// Merge sorted files
+    ///
+    /// * `intermediate_input` - the files are intermediate files, which are removed once merged.
+    ///   Otherwise the files are the input files of the merge
+    /// * `final_output` - the result is the output of the sort or merge, with prefix and suffix
+    ///   lines. Otherwise the result is an intermediate file
+    pub(crate) fn internal_merge(files: Vec<PathBuf>, config: &Config, intermediate_input: bool, final_output: bool) -> Result<(PathBuf, usize), anyhow::Error> {
         log::info!("Merging {} sorted files, thread: {}", files.len(), thread::current().name().unwrap_or("unnamed"));
         let mut merged_len: usize = 0;
         let merged_file = create_tmp_file(config);
         let (persisted_merged_file, path) = merged_file.keep()?;
         let mut merged_writer = BufWriter::new(persisted_merged_file);
-        if add_prefix_suffix {
+        if final_output {
             for prefix in config.prefix() {
                 writeln!(merged_writer, "{}", prefix)?;
                 merged_len += 1;
             }
         }
 
-        if files.len() == 1 {
+        // lines of intermediate files are prefixed with their position when records keep their
+        // input order
+        let prefixed_input = config.positioned() && intermediate_input;
+        let prefixed_output = config.positioned() && !final_output;
+        let tombstone = if final_output { config.tombstone().as_ref() } else { None };
+        // an intermediate file is copied as is, its lines are already parsed and unique, and
+        // tombstones are dropped from the final output only
+        if files.len() == 1 && intermediate_input && prefixed_input == prefixed_output && tombstone.is_none() {
             let file = File::open(files[0].clone()).with_context(|| format!("path: {}", files[0].display()))?;
             let mut reader = BufReader::new(file);
             let mut line = String::new();
@@ -437,19 +528,22 @@ impl Sort {
                 line = String::new();
                 merged_len += 1;
             }
-            std::fs::remove_file(files[0].clone())?;
+            if intermediate_input {
+                std::fs::remove_file(files[0].clone())?;
+            }
         } else {
-            let mut unmerged_files: BinaryHeap<UnmergedChunkFile> = files.into_iter()
-                .map(
-                    |path| UnmergedChunkFile::new(
-                        path,
-                        config.fields(),
-                        config.field_separator(),
-                        config.order().clone(),
-                    )
-                        .unwrap()
-                )
-                .collect();
+            let mut unique_filter = UniqueFilter::new(config);
+            let mut unmerged_files = BinaryHeap::with_capacity(files.len());
+            for (file_index, path) in files.into_iter().enumerate() {
+                let positions = if !config.positioned() {
+                    Positions::Ignored
+                } else if intermediate_input {
+                    Positions::Prefixed
+                } else {
+                    Positions::Counted(file_index)
+                };
+                unmerged_files.push(UnmergedChunkFile::new(path, config, positions)?);
+            }
             while unmerged_files.len() > 1 {
                 let mut current_min = unmerged_files.pop().unwrap();
                 let unmerged_min = unmerged_files.peek().unwrap();
@@ -457,13 +551,13 @@ impl Sort {
                 let mut current_min_done = false;
                 // comparison operators are flipped to work with BinaryHeap (Max Heap)
                 while &current_min >= unmerged_min {
-                    if let Some(line_record) = current_min.line_record() {
-                        let line = line_record.line();
-                        merged_writer.write_all(line.as_bytes())?;
-                        merged_len += 1;
+                    if let Some(line_record) = current_min.line_record()? {
+                        if let Some(line_record) = unique_filter.push(line_record) {
+                            merged_len += Self::write_merged_line_record(&mut merged_writer, line_record, prefixed_output, tombstone)?;
+                        }
                     } else {
                         current_min_done = true;
-                        if remove_merged {
+                        if intermediate_input {
                             std::fs::remove_file(current_min.path())?;
                         }
                         break;
@@ -475,19 +569,24 @@ impl Sort {
             }
             let mut current_min = unmerged_files.pop().unwrap();
             loop {
-                if let Some(line_record) = current_min.line_record() {
-                    let line = line_record.line();
-                    merged_writer.write_all(line.as_bytes())?;
-                    merged_len += 1;
+                if let Some(line_record) = current_min.line_record()? {
+                    if let Some(line_record) = unique_filter.push(line_record) {
+                        merged_len += Self::write_merged_line_record(&mut merged_writer, line_record, prefixed_output, tombstone)?;
+                    }
                 } else {
-                    std::fs::remove_file(current_min.path())?;
+                    if intermediate_input {
+                        std::fs::remove_file(current_min.path())?;
+                    }
                     break;
                 }
             }
+            if let Some(line_record) = unique_filter.finish() {
+                merged_len += Self::write_merged_line_record(&mut merged_writer, line_record, prefixed_output, tombstone)?;
+            }
 
             log::info!("Finished merging sorted files, thread: {}, merged length: {} lines", thread::current().name().unwrap_or("unnamed"), merged_len);
         }
-        if add_prefix_suffix {
+        if final_output {
             for suffix in config.suffix() {
                 writeln!(merged_writer, "{}", suffix)?;
                 merged_len += 1;
@@ -496,7 +595,30 @@ impl Sort {
         Ok((path, merged_len))
     }
 
-    fn internal_sort(input_files: &Vec<PathBuf>, config: &Config, output: &Path) -> Result<(), anyhow::Error> {
+    /// Write the line of the record unless it is deleted by the tombstone. Returns the number of
+    /// lines written.
+    fn write_merged_line_record(writer: &mut impl Write, line_record: LineRecord, prefixed: bool, tombstone: Option<&Tombstone>) -> Result<usize, anyhow::Error> {
+        if tombstone.is_some_and(|tombstone| tombstone.is_deleted(&line_record)) {
+            Ok(0)
+        } else {
+            Self::write_line_record(writer, line_record, prefixed)?;
+            Ok(1)
+        }
+    }
+
+    /// Write the line of the record, prefixed with its position in an intermediate file of a
+    /// stable sort
+    pub(crate) fn write_line_record(writer: &mut impl Write, line_record: LineRecord, prefixed: bool) -> Result<(), anyhow::Error> {
+        if prefixed {
+            if let Some(position) = line_record.position() {
+                writer.write_all(position.prefix().as_bytes())?;
+            }
+        }
+        writer.write_all(line_record.line().as_bytes())?;
+        Ok(())
+    }
+
+    fn internal_sort(input_files: &[PathBuf], config: &Config, output: &Path) -> Result<(), anyhow::Error> {
         log::info!("Start parallel sort");
         let mut thread_pool_builder = ThreadPoolBuilder::new();
         let mut sorting_pool = thread_pool_builder
@@ -507,10 +629,10 @@ impl Sort {
             .build()
             .unwrap();
 
-        sorting_pool.set_thread_local(&CONFIG, Some(config.clone()));
+        sorting_pool.set_thread_local(&CONFIG, Some(Arc::new(config.clone())));
 
-        for path in input_files {
-            for chunk in ChunkIterator::new(path, config.chunk_size_bytes(), config.endl()).unwrap() {
+        for (file_index, path) in input_files.iter().enumerate() {
+            for chunk in ChunkIterator::new(path, file_index, config.chunk_size_bytes(), config.endl()).unwrap() {
                 let sort_command = Box::new(SortCommand::new(Some(chunk)));
                 sorting_pool.submit(sort_command);
             }
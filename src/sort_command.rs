@@ -2,16 +2,17 @@ use std::cell::RefCell;
 use std::cmp::{max, Reverse};
 use std::collections::BinaryHeap;
 use std::fs::File;
-use std::io::{BufRead, BufReader, BufWriter, Read, Seek, SeekFrom, Write};
+use std::io::{BufRead, BufReader, BufWriter, Read, Seek, SeekFrom};
 
 use anyhow::{anyhow, Context};
 use command_executor::command::Command;
 
 use crate::chunk_iterator::Chunk;
 use crate::config::Config;
-use crate::line_record::LineRecord;
+use crate::line_record::{LineRecord, Position};
 use crate::sort::{create_tmp_file, get_line_capacity, get_line_records_capacity, get_tl_config, set_line_capacity, set_line_records_capacity, Sort, SORTED_FILES};
 use crate::sorted_chunk_file::SortedChunkFile;
+use crate::unique::UniqueFilter;
 
 pub(crate) struct SortCommand {
     chunk: Option<Chunk>,
@@ -33,7 +34,7 @@ impl SortCommand {
         let mut buf_writer = BufWriter::new(chunk_file);
 
         for line_record in chunk {
-            buf_writer.write_all(line_record.line().as_bytes()).unwrap();
+            Sort::write_line_record(&mut buf_writer, line_record, config.positioned()).unwrap();
         }
 
         sorted_files
@@ -56,27 +57,22 @@ impl SortCommand {
                 let config = get_tl_config();
 
                 let mut n = 0;
+                let mut line_offset = file_chunk.offset();
                 let mut line = String::with_capacity(line_capacity);
-                while reader.read_line(&mut line)? != 0 {
+                loop {
+                    let bytes = reader.read_line(&mut line)?;
+                    if bytes == 0 {
+                        break;
+                    }
+                    let position = Position::new(file_chunk.file_index(), line_offset);
+                    line_offset += bytes as u64;
                     n += 1;
-                    if config.ignore_empty() && line.trim().is_empty() {
+                    if config.ignored(&line) {
                         line.clear();
                         continue;
                     }
-
-                    if let Some(r) = config.ignore_lines() {
-                        if r.is_match(line.trim()) {
-                            line.clear();
-                            continue;
-                        }
-                    }
                     line_capacity = max(line.len(), line_capacity);
-                    let line_record = LineRecord::new(
-                        line,
-                        config.fields(),
-                        config.field_separator(),
-                        config.order().clone(),
-                    )
+                    let mut line_record = LineRecord::from_config(line, &config)
                         .with_context(||
                             format!(
                                 "file: {}, chunk offset: {}, line within chunk: {}",
@@ -85,6 +81,9 @@ impl SortCommand {
                                 n
                             )
                         )?;
+                    if config.positioned() {
+                        line_record = line_record.with_position(position);
+                    }
                     line_records.push(line_record);
                     line = String::with_capacity(line_capacity);
                 }
@@ -100,7 +99,8 @@ impl Command for SortCommand {
     fn execute(&self) -> Result<(), anyhow::Error> {
         let config = get_tl_config();
         let mut chunk = self.read_records()?;
-        chunk.sort();
+        chunk.sort_by(|a, b| a.compare(b, &config));
+        let chunk = UniqueFilter::filter(&config, chunk);
         SORTED_FILES.with(
             |sorted_files| {
                 let chunk_size = chunk.len();
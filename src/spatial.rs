@@ -0,0 +1,113 @@
+use anyhow::anyhow;
+
+/// Space filling curve used to order points by spatial locality
+#[derive(Clone, Debug)]
+pub enum Curve {
+    /// Hilbert curve, better locality
+    Hilbert,
+    /// Z-order (Morton) curve, faster to compute
+    ZOrder,
+}
+
+impl Curve {
+    /// Compute the position of the point on the curve. Latitude and longitude are quantized to
+    /// `precision` bits each, so the result has `2 * precision` significant bits.
+    pub(crate) fn index(&self, latitude: f64, longitude: f64, precision: u8) -> Result<u64, anyhow::Error> {
+        if !(1..=32).contains(&precision) {
+            return Err(anyhow!("Precision must be between 1 and 32 bits, got: {}", precision));
+        }
+        if latitude.is_nan() || longitude.is_nan() {
+            return Err(anyhow!("Invalid coordinates, latitude: {}, longitude: {}", latitude, longitude));
+        }
+        let x = Self::quantize(longitude, -180.0, 180.0, precision);
+        let y = Self::quantize(latitude, -90.0, 90.0, precision);
+        match self {
+            Curve::Hilbert => {
+                Ok(Self::hilbert_index(x, y, precision))
+            }
+            Curve::ZOrder => {
+                Ok(Self::z_order_index(x, y))
+            }
+        }
+    }
+
+    fn quantize(value: f64, min: f64, max: f64, precision: u8) -> u64 {
+        let cells = (1_u64 << precision) as f64;
+        let cell = ((value - min) / (max - min) * cells).floor();
+        cell.clamp(0.0, cells - 1.0) as u64
+    }
+
+    fn z_order_index(x: u64, y: u64) -> u64 {
+        let mut index = 0;
+        for bit in 0..32 {
+            index |= ((x >> bit) & 1) << (2 * bit);
+            index |= ((y >> bit) & 1) << (2 * bit + 1);
+        }
+        index
+    }
+
+    fn hilbert_index(mut x: u64, mut y: u64, precision: u8) -> u64 {
+        let n = 1_u64 << precision;
+        let mut index = 0;
+        let mut s = n / 2;
+        while s > 0 {
+            let rx = ((x & s) > 0) as u64;
+            let ry = ((y & s) > 0) as u64;
+            index += s * s * ((3 * rx) ^ ry);
+            if ry == 0 {
+                if rx == 1 {
+                    x = n - 1 - x;
+                    y = n - 1 - y;
+                }
+                std::mem::swap(&mut x, &mut y);
+            }
+            s /= 2;
+        }
+        index
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::spatial::Curve;
+
+    #[test]
+    fn test_z_order() {
+        assert_eq!(Curve::z_order_index(0b11, 0b00), 0b0101);
+        assert_eq!(Curve::z_order_index(0b00, 0b11), 0b1010);
+        assert_eq!(Curve::z_order_index(u32::MAX as u64, u32::MAX as u64), u64::MAX);
+    }
+
+    #[test]
+    fn test_hilbert() {
+        // order 1 curve visits (0, 0), (0, 1), (1, 1), (1, 0)
+        assert_eq!(Curve::hilbert_index(0, 0, 1), 0);
+        assert_eq!(Curve::hilbert_index(0, 1, 1), 1);
+        assert_eq!(Curve::hilbert_index(1, 1, 1), 2);
+        assert_eq!(Curve::hilbert_index(1, 0, 1), 3);
+
+        // consecutive positions on the curve are adjacent cells
+        let precision = 4;
+        let n = 1_u64 << precision;
+        let mut cells = vec![(0, 0); (n * n) as usize];
+        for x in 0..n {
+            for y in 0..n {
+                cells[Curve::hilbert_index(x, y, precision) as usize] = (x, y);
+            }
+        }
+        for pair in cells.windows(2) {
+            let (a, b) = (pair[0], pair[1]);
+            assert_eq!(a.0.abs_diff(b.0) + a.1.abs_diff(b.1), 1);
+        }
+    }
+
+    #[test]
+    fn test_index() {
+        assert!(Curve::Hilbert.index(0.0, 0.0, 0).is_err());
+        assert!(Curve::Hilbert.index(0.0, 0.0, 33).is_err());
+        assert!(Curve::ZOrder.index(f64::NAN, 0.0, 16).is_err());
+        assert_eq!(Curve::ZOrder.index(-90.0, -180.0, 32).unwrap(), 0);
+        assert_eq!(Curve::ZOrder.index(90.0, 180.0, 32).unwrap(), u64::MAX);
+        assert_eq!(Curve::Hilbert.index(100.0, 200.0, 32).unwrap(), Curve::Hilbert.index(90.0, 180.0, 32).unwrap());
+    }
+}
@@ -0,0 +1,118 @@
+use std::cmp::Ordering;
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
+
+use crate::config::Config;
+use crate::line_record::LineRecord;
+
+/// Which record to keep of the records with equal keys, similar to GNU `sort -u`
+#[derive(Clone, Debug)]
+pub enum Unique {
+    /// Keep the first record in the input order, first by the order of the input files and then
+    /// by the order of the lines in each file
+    KeepFirst,
+    /// Keep the last record in the input order
+    KeepLast,
+    /// Keep any of the records, the fastest option
+    KeepAny,
+}
+
+/// Predicate marking the lines of deleted records
+#[derive(Clone)]
+pub(crate) struct Tombstone(Arc<dyn Fn(&str) -> bool + Send + Sync>);
+
+impl Tombstone {
+    pub(crate) fn new<F>(tombstone: F) -> Tombstone
+    where
+        F: Fn(&str) -> bool + Send + Sync + 'static,
+    {
+        Tombstone(Arc::new(tombstone))
+    }
+
+    pub(crate) fn is_deleted(&self, line_record: &LineRecord) -> bool {
+        (self.0)(line_record.text())
+    }
+}
+
+impl Debug for Tombstone {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Tombstone")
+    }
+}
+
+/// Removes records with keys equal to the keys of the previous record from a sorted sequence
+pub(crate) struct UniqueFilter<'a> {
+    config: &'a Config,
+    pending: Option<LineRecord>,
+}
+
+impl<'a> UniqueFilter<'a> {
+    pub(crate) fn new(config: &'a Config) -> UniqueFilter<'a> {
+        UniqueFilter {
+            config,
+            pending: None,
+        }
+    }
+
+    /// Add the next record of the sorted sequence. Returns a record that is ready for output.
+    pub(crate) fn push(&mut self, line_record: LineRecord) -> Option<LineRecord> {
+        if !self.config.deduplicated() {
+            return Some(line_record);
+        }
+        match self.pending.take() {
+            Some(pending) if pending.keys_cmp(&line_record, self.config) == Ordering::Equal => {
+                self.pending = Some(self.select(pending, line_record));
+                None
+            }
+            pending => {
+                self.pending = Some(line_record);
+                pending
+            }
+        }
+    }
+
+    /// The last record ready for output
+    pub(crate) fn finish(&mut self) -> Option<LineRecord> {
+        self.pending.take()
+    }
+
+    /// Remove the records with equal keys from a sorted chunk
+    pub(crate) fn filter(config: &Config, line_records: Vec<LineRecord>) -> Vec<LineRecord> {
+        if !config.deduplicated() {
+            return line_records;
+        }
+        let mut unique_filter = UniqueFilter::new(config);
+        let mut result = Vec::with_capacity(line_records.len());
+        for line_record in line_records {
+            result.extend(unique_filter.push(line_record));
+        }
+        result.extend(unique_filter.finish());
+        result
+    }
+
+    fn select(&self, current: LineRecord, next: LineRecord) -> LineRecord {
+        let next_is_later = match (current.position(), next.position()) {
+            (Some(current_position), Some(next_position)) => { next_position > current_position }
+            _ => { true }
+        };
+        if let Some(priorities) = self.config.priorities() {
+            let priority = |line_record: &LineRecord| {
+                line_record.position()
+                    .as_ref()
+                    .and_then(|position| priorities.get(position.file_index()).copied())
+                    .unwrap_or(0)
+            };
+            return match priority(&next).cmp(&priority(&current)) {
+                Ordering::Greater => { next }
+                Ordering::Equal if next_is_later => { next }
+                _ => { current }
+            };
+        }
+        match self.config.unique() {
+            Some(Unique::KeepFirst) if next_is_later => { current }
+            Some(Unique::KeepLast) if !next_is_later => { current }
+            Some(Unique::KeepAny) => { current }
+            _ => { next }
+        }
+    }
+}
@@ -5,60 +5,74 @@ use std::path::PathBuf;
 
 use anyhow::Context;
 
-use crate::field::Field;
-use crate::line_record::LineRecord;
-use crate::order::Order;
+use crate::config::Config;
+use crate::line_record::{LineRecord, Position};
 
+/// Source of the positions of the lines of a file, used by the stable sort
 #[derive(Debug)]
-pub(crate) struct UnmergedChunkFile {
+pub(crate) enum Positions {
+    /// Positions are not used
+    Ignored,
+    /// Lines of an intermediate file are prefixed with their position
+    Prefixed,
+    /// Positions are the offsets of the lines in the input file with the index
+    Counted(usize),
+}
+
+#[derive(Debug)]
+pub(crate) struct UnmergedChunkFile<'a> {
     path: PathBuf,
     reader: BufReader<File>,
     head: Option<LineRecord>,
-    fields: Vec<Field>,
-    field_separator: char,
-    order: Order,
+    config: &'a Config,
+    positions: Positions,
+    offset: u64,
 }
 
-impl UnmergedChunkFile {
-    pub(crate) fn new(path: PathBuf, fields: &Vec<Field>, field_separator: char, order: Order) -> Result<UnmergedChunkFile, anyhow::Error> {
+impl<'a> UnmergedChunkFile<'a> {
+    pub(crate) fn new(path: PathBuf, config: &'a Config, positions: Positions) -> Result<UnmergedChunkFile<'a>, anyhow::Error> {
         let file = File::open(path.clone()).with_context(|| format!("path: {}", path.display()))?;
-        let mut reader = BufReader::new(file);
-        let mut line = String::new();
-        let bytes = reader.read_line(&mut line)?;
-        if bytes > 0 {
-            Ok(
-                UnmergedChunkFile {
-                    path,
-                    reader,
-                    head: Some(LineRecord::new(line, fields, field_separator, order.clone())?),
-                    fields: fields.clone(),
-                    field_separator,
-                    order,
-                }
-            )
-        } else {
-            Ok(
-                UnmergedChunkFile {
-                    path,
-                    reader,
-                    head: None,
-                    fields: fields.clone(),
-                    field_separator,
-                    order,
-                }
-            )
-        }
+        let mut unmerged_chunk_file = UnmergedChunkFile {
+            path,
+            reader: BufReader::new(file),
+            head: None,
+            config,
+            positions,
+            offset: 0,
+        };
+        unmerged_chunk_file.head = unmerged_chunk_file.read_line_record()
+            .with_context(|| format!("path: {}", unmerged_chunk_file.path.display()))?;
+        Ok(unmerged_chunk_file)
     }
 
-    pub(crate) fn line_record(&mut self) -> Option<LineRecord> {
+    fn read_line_record(&mut self) -> Result<Option<LineRecord>, anyhow::Error> {
         let mut line = String::new();
-        let bytes = self.reader.read_line(&mut line).ok()?;
-        let line_record = if bytes > 0 {
-            LineRecord::new(line, &self.fields, self.field_separator, self.order.clone()).ok()
-        } else {
-            None
+        let bytes = self.reader.read_line(&mut line)?;
+        if bytes == 0 {
+            return Ok(None);
+        }
+        let line_record = match self.positions {
+            Positions::Ignored => {
+                LineRecord::from_config(line, self.config)?
+            }
+            Positions::Prefixed => {
+                let (position, prefix_len) = Position::parse(&line)?;
+                line.drain(..prefix_len);
+                LineRecord::from_config(line, self.config)?.with_position(position)
+            }
+            Positions::Counted(file_index) => {
+                let position = Position::new(file_index, self.offset);
+                LineRecord::from_config(line, self.config)?.with_position(position)
+            }
         };
-        std::mem::replace(&mut self.head, line_record)
+        self.offset += bytes as u64;
+        Ok(Some(line_record))
+    }
+
+    pub(crate) fn line_record(&mut self) -> Result<Option<LineRecord>, anyhow::Error> {
+        let line_record = self.read_line_record()
+            .with_context(|| format!("path: {}", self.path.display()))?;
+        Ok(std::mem::replace(&mut self.head, line_record))
     }
 
     pub(crate) fn path(&self) -> PathBuf {
@@ -66,27 +80,27 @@ impl UnmergedChunkFile {
     }
 }
 
-impl Eq for UnmergedChunkFile {}
+impl Eq for UnmergedChunkFile<'_> {}
 
-impl PartialEq<Self> for UnmergedChunkFile {
+impl PartialEq<Self> for UnmergedChunkFile<'_> {
     fn eq(&self, other: &Self) -> bool {
         if self.head.is_none() && other.head.is_none() {
             true
         } else if self.head.is_none() || other.head.is_none() {
             false
         } else {
-            other.head.as_ref().unwrap().eq(self.head.as_ref().unwrap())
+            other.head.as_ref().unwrap().compare(self.head.as_ref().unwrap(), self.config) == Ordering::Equal
         }
     }
 }
 
-impl PartialOrd<Self> for UnmergedChunkFile {
+impl PartialOrd<Self> for UnmergedChunkFile<'_> {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         Some(self.cmp(other))
     }
 }
 
-impl Ord for UnmergedChunkFile {
+impl Ord for UnmergedChunkFile<'_> {
     fn cmp(&self, other: &Self) -> Ordering {
         if self.head.is_none() && other.head.is_none() {
             Ordering::Equal
@@ -96,7 +110,7 @@ impl Ord for UnmergedChunkFile {
         } else if self.head.is_some() && other.head.is_none() {
             Ordering::Less
         } else {
-            other.head.as_ref().unwrap().cmp(self.head.as_ref().unwrap())
+            other.head.as_ref().unwrap().compare(self.head.as_ref().unwrap(), self.config)
         }
     }
 }
\ No newline at end of file
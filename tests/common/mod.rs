@@ -1,9 +1,13 @@
 use std::fs;
 use std::path::PathBuf;
 use std::str::FromStr;
-use std::io::{BufRead, BufReader};
+use std::io::{BufRead, BufReader, BufWriter, Write};
 use std::fs::File;
 use data_encoding::HEXLOWER;
+use text_file_sort::field::Field;
+use text_file_sort::field_type::FieldType;
+use text_file_sort::order::Order;
+use text_file_sort::sort::Sort;
 
 pub fn setup() {
     let results_dir_path = PathBuf::from_str("./target/results/").unwrap();
@@ -40,3 +44,109 @@ pub fn temp_file_name(dir: &str) -> PathBuf {
     result.push(name);
     result
 }
+
+#[allow(dead_code)]
+pub fn write_lines(path: PathBuf, lines: &[&str]) -> Result<(), anyhow::Error> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    for line in lines {
+        writeln!(writer, "{}", line)?;
+    }
+    Ok(())
+}
+
+#[allow(dead_code)]
+pub fn write_input<S: AsRef<str>>(lines: &[S]) -> Result<PathBuf, anyhow::Error> {
+    let path = temp_file_name("./target/results/");
+    let lines: Vec<&str> = lines.iter().map(|line| line.as_ref()).collect();
+    write_lines(path.clone(), &lines)?;
+    Ok(path)
+}
+
+/// Lines of `records` records: a key cycling through `keys` values, the seed and the record
+/// number. Records with equal keys are distinguished by the seed and the record number.
+#[allow(dead_code)]
+pub fn keyed_lines(records: usize, keys: usize, seed: usize) -> Vec<String> {
+    (0..records)
+        .map(|i| format!("{}\t{}\t{}", (i * 7919 + seed) % keys, seed, i))
+        .collect()
+}
+
+/// The field with the key followed by the ascending id in the first field, to break ties
+#[allow(dead_code)]
+pub fn with_id(field: Field) -> Vec<Field> {
+    vec![field, Field::new(1, FieldType::Integer).with_order(Order::Asc)]
+}
+
+fn create_sort(input_files: Vec<PathBuf>, output: PathBuf, fields: &[Field], order: &Order, field_separator: char) -> Sort {
+    let mut text_file_sort = Sort::new(input_files, output);
+    text_file_sort.with_fields(fields.to_vec());
+    text_file_sort.with_order(order.clone());
+    text_file_sort.with_field_separator(field_separator);
+    text_file_sort.with_tmp_dir(PathBuf::from("./target/results/"));
+    text_file_sort
+}
+
+/// Sort the lines by the fields and check that the result is sorted
+#[allow(dead_code)]
+pub fn sort_lines(lines: &[&str], fields: Vec<Field>, order: Order) -> Result<Vec<String>, anyhow::Error> {
+    sort_separated_lines(lines, fields, order, '\t')
+}
+
+/// Same as [sort_lines] for lines with fields separated by `field_separator`
+#[allow(dead_code)]
+pub fn sort_separated_lines(
+    lines: &[&str],
+    fields: Vec<Field>,
+    order: Order,
+    field_separator: char,
+) -> Result<Vec<String>, anyhow::Error> {
+    setup();
+    let input_path = write_input(lines)?;
+    let output_path = temp_file_name("./target/results/");
+    create_sort(vec![input_path.clone()], output_path.clone(), &fields, &order, field_separator).sort()?;
+    assert!(
+        create_sort(vec![output_path.clone()], PathBuf::new(), &fields, &order, field_separator).check()?,
+        "sorted output fails the check"
+    );
+
+    let lines = read_lines(output_path.clone())?;
+    fs::remove_file(input_path)?;
+    fs::remove_file(output_path)?;
+    Ok(lines)
+}
+
+/// Merge the sorted inputs by the fields
+#[allow(dead_code)]
+pub fn merge_lines(inputs: &[&[&str]], fields: Vec<Field>, order: Order) -> Result<Vec<String>, anyhow::Error> {
+    setup();
+    let mut input_paths = Vec::new();
+    for lines in inputs {
+        input_paths.push(write_input(lines)?);
+    }
+    let output_path = temp_file_name("./target/results/");
+    create_sort(input_paths.clone(), output_path.clone(), &fields, &order, '\t').merge()?;
+
+    let lines = read_lines(output_path.clone())?;
+    for input_path in input_paths {
+        fs::remove_file(input_path)?;
+    }
+    fs::remove_file(output_path)?;
+    Ok(lines)
+}
+
+/// Check if the lines are sorted by the fields
+#[allow(dead_code)]
+pub fn check_lines(lines: &[&str], fields: Vec<Field>, order: Order) -> Result<bool, anyhow::Error> {
+    setup();
+    let input_path = write_input(lines)?;
+    let sorted = create_sort(vec![input_path.clone()], PathBuf::new(), &fields, &order, '\t').check()?;
+    fs::remove_file(input_path)?;
+    Ok(sorted)
+}
+
+/// Same as [sort_lines] but return the first field of each line, for example the id of the record
+#[allow(dead_code)]
+pub fn sort_ids(lines: &[&str], fields: Vec<Field>, order: Order) -> Result<Vec<String>, anyhow::Error> {
+    let lines = sort_lines(lines, fields, order)?;
+    Ok(lines.iter().map(|line| line.split('\t').next().unwrap().to_string()).collect())
+}
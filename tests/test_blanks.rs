@@ -0,0 +1,60 @@
+use text_file_sort::blanks::Blanks;
+use text_file_sort::field::Field;
+use text_file_sort::field_type::FieldType;
+use text_file_sort::order::Order;
+
+mod common;
+
+#[test]
+fn test_ignore_leading_blanks() -> Result<(), anyhow::Error> {
+    let input = ["1\t  b", "2\ta  ", "3\t a", "4\ta"];
+    let lines = common::sort_lines(&input, common::with_id(Field::new(2, FieldType::String).with_ignore_leading_blanks(true)), Order::Asc)?;
+    assert_eq!(lines, vec!["3\t a", "4\ta", "2\ta  ", "1\t  b"]);
+
+    let lines = common::sort_lines(&input, common::with_id(Field::new(2, FieldType::String).with_ignore_trailing_blanks(true)), Order::Asc)?;
+    assert_eq!(lines, vec!["1\t  b", "3\t a", "2\ta  ", "4\ta"]);
+
+    let lines = common::sort_lines(&input, common::with_id(Field::new(2, FieldType::String).with_ignore_blanks(true)), Order::Asc)?;
+    assert_eq!(lines, vec!["2\ta  ", "3\t a", "4\ta", "1\t  b"]);
+    Ok(())
+}
+
+#[test]
+fn test_blanks() -> Result<(), anyhow::Error> {
+    // the first value starts with a no-break space
+    let input = ["1\t\u{a0}b", "2\t c"];
+    let lines = common::sort_lines(
+        &input,
+        common::with_id(Field::new(2, FieldType::String)
+            .with_ignore_leading_blanks(true)
+            .with_blanks(Blanks::Ascii)),
+        Order::Asc,
+    )?;
+    assert_eq!(lines, vec!["2\t c", "1\t\u{a0}b"]);
+
+    let lines = common::sort_lines(
+        &input,
+        common::with_id(Field::new(2, FieldType::String)
+            .with_ignore_leading_blanks(true)
+            .with_blanks(Blanks::Unicode)),
+        Order::Asc,
+    )?;
+    assert_eq!(lines, vec!["1\t\u{a0}b", "2\t c"]);
+    Ok(())
+}
+
+#[test]
+fn test_blanks_merge_check() -> Result<(), anyhow::Error> {
+    let field = Field::new(2, FieldType::String).with_ignore_leading_blanks(true);
+    let merged = common::merge_lines(&[&["3\t a", "1\t  b"], &["4\ta", "2\ta  "]], common::with_id(field.clone()), Order::Asc)?;
+    assert_eq!(merged, vec!["3\t a", "4\ta", "2\ta  ", "1\t  b"]);
+    assert!(common::check_lines(&["3\t a", "4\ta", "2\ta  ", "1\t  b"], common::with_id(field.clone()), Order::Asc)?);
+    assert!(!common::check_lines(&["1\t  b", "3\t a"], common::with_id(field), Order::Asc)?);
+
+    // the first value starts with a no-break space
+    let input = ["2\t c", "1\t\u{a0}b"];
+    let field = Field::new(2, FieldType::String).with_ignore_leading_blanks(true);
+    assert!(common::check_lines(&input, common::with_id(field.clone().with_blanks(Blanks::Ascii)), Order::Asc)?);
+    assert!(!common::check_lines(&input, common::with_id(field.with_blanks(Blanks::Unicode)), Order::Asc)?);
+    Ok(())
+}
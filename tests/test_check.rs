@@ -48,4 +48,24 @@ fn test_check_not_sorted() -> Result<(), anyhow::Error> {
     assert!(!result);
     fs::remove_file(random_path)?;
     Ok(())
-}
\ No newline at end of file
+}
+
+fn check_ignored_lines(lines: &[&str]) -> Result<bool, anyhow::Error> {
+    let input_path = common::write_input(lines)?;
+    let mut text_file_sort = Sort::new(vec![input_path.clone()], PathBuf::new());
+    text_file_sort.add_field(Field::new(1, FieldType::Integer));
+    text_file_sort.with_ignore_empty();
+    text_file_sort.with_ignore_lines(regex::Regex::new("^#")?);
+    let sorted = text_file_sort.check()?;
+    fs::remove_file(input_path)?;
+    Ok(sorted)
+}
+
+#[test]
+fn test_check_ignored_lines() -> Result<(), anyhow::Error> {
+    common::setup();
+    assert!(check_ignored_lines(&["1", "# comment", "", "2", "10"])?);
+    // the lines after an ignored line are checked
+    assert!(!check_ignored_lines(&["1", "# comment", "3", "2"])?);
+    Ok(())
+}
@@ -0,0 +1,49 @@
+use text_file_sort::collation::{Collation, Strength};
+use text_file_sort::field::Field;
+use text_file_sort::field_type::FieldType;
+use text_file_sort::order::Order;
+
+mod common;
+
+#[test]
+fn test_collation() -> Result<(), anyhow::Error> {
+    let input = ["1\tZyrard\u{f3}w", "2\tzebra", "3\tZ\u{fc}rich", "4\t\u{c5}re", "5\tAmsterdam", "6\t\u{d6}rebro"];
+    let root = Field::new(2, FieldType::String)
+        .with_collation(Collation::root(Strength::Tertiary));
+    let lines = common::sort_lines(&input, vec![root], Order::Asc)?;
+    assert_eq!(
+        lines,
+        vec!["5\tAmsterdam", "4\t\u{c5}re", "6\t\u{d6}rebro", "2\tzebra", "3\tZ\u{fc}rich", "1\tZyrard\u{f3}w"]
+    );
+
+    let swedish = Field::new(2, FieldType::String)
+        .with_collation(Collation::new("sv", Strength::Tertiary)?);
+    let lines = common::sort_lines(&input, vec![swedish], Order::Asc)?;
+    assert_eq!(
+        lines,
+        vec!["5\tAmsterdam", "2\tzebra", "1\tZyrard\u{f3}w", "3\tZ\u{fc}rich", "4\t\u{c5}re", "6\t\u{d6}rebro"]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_collation_merge_check() -> Result<(), anyhow::Error> {
+    let swedish = Field::new(2, FieldType::String)
+        .with_collation(Collation::new("sv", Strength::Tertiary)?);
+    let merged = common::merge_lines(
+        &[&["5\tAmsterdam", "1\tZyrard\u{f3}w", "4\t\u{c5}re"], &["2\tzebra", "3\tZ\u{fc}rich", "6\t\u{d6}rebro"]],
+        vec![swedish.clone()],
+        Order::Asc,
+    )?;
+    let sorted = ["5\tAmsterdam", "2\tzebra", "1\tZyrard\u{f3}w", "3\tZ\u{fc}rich", "4\t\u{c5}re", "6\t\u{d6}rebro"];
+    assert_eq!(merged, sorted);
+    assert!(common::check_lines(&sorted, vec![swedish.clone()], Order::Asc)?);
+
+    // Å follows Z in Swedish but not in the root collation
+    let input = ["4\t\u{c5}re", "2\tzebra"];
+    let root = Field::new(2, FieldType::String)
+        .with_collation(Collation::root(Strength::Tertiary));
+    assert!(common::check_lines(&input, vec![root], Order::Asc)?);
+    assert!(!common::check_lines(&input, vec![swedish], Order::Asc)?);
+    Ok(())
+}
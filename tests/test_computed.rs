@@ -0,0 +1,62 @@
+use anyhow::anyhow;
+use text_file_sort::field::Field;
+use text_file_sort::field_type::FieldType;
+use text_file_sort::order::Order;
+
+mod common;
+
+fn part<'a>(parts: &[&'a str], index: usize) -> Result<&'a str, anyhow::Error> {
+    parts.get(index - 1).copied().ok_or_else(|| anyhow!("Missing field: {}", index))
+}
+
+#[test]
+fn test_computed_length() -> Result<(), anyhow::Error> {
+    let length = Field::new_computed(FieldType::Integer, |parts| {
+        Ok(part(parts, 2)?.chars().count().to_string())
+    });
+    let lines = common::sort_lines(
+        &["1\tTel Aviv", "2\tHaifa", "3\tBe'er Sheva", "4\tEilat"],
+        vec![length, Field::new(1, FieldType::Integer)],
+        Order::Asc,
+    )?;
+    assert_eq!(lines, vec!["2\tHaifa", "4\tEilat", "1\tTel Aviv", "3\tBe'er Sheva"]);
+    Ok(())
+}
+
+#[test]
+fn test_computed_coalesce() -> Result<(), anyhow::Error> {
+    let coalesce = Field::new_computed(FieldType::Integer, |parts| {
+        let value = if part(parts, 2)?.is_empty() { part(parts, 3)? } else { part(parts, 2)? };
+        Ok(value.to_string())
+    });
+    let lines = common::sort_lines(
+        &["a\t30\t1", "b\t\t20", "c\t10\t40", "d\t\t5"],
+        vec![coalesce],
+        Order::Asc,
+    )?;
+    assert_eq!(lines, vec!["d\t\t5", "c\t10\t40", "b\t\t20", "a\t30\t1"]);
+    Ok(())
+}
+
+#[test]
+fn test_computed_concatenation() -> Result<(), anyhow::Error> {
+    let concatenation = Field::new_computed(FieldType::String, |parts| {
+        Ok(format!("{}{}", part(parts, 2)?.to_lowercase(), part(parts, 1)?))
+    });
+    let lines = common::sort_lines(
+        &["2\tB", "1\tb", "1\tA", "3\ta"],
+        vec![concatenation],
+        Order::Asc,
+    )?;
+    assert_eq!(lines, vec!["1\tA", "3\ta", "1\tb", "2\tB"]);
+    Ok(())
+}
+
+#[test]
+fn test_computed_missing_field() -> Result<(), anyhow::Error> {
+    let length = Field::new_computed(FieldType::Integer, |parts| {
+        Ok(part(parts, 2)?.chars().count().to_string())
+    });
+    assert!(common::check_lines(&["1\tHaifa", "2"], vec![length], Order::Asc).is_err());
+    Ok(())
+}
@@ -0,0 +1,37 @@
+use text_file_sort::field::Field;
+use text_file_sort::field_encoding::FieldEncoding;
+use text_file_sort::field_type::FieldType;
+use text_file_sort::order::Order;
+
+mod common;
+
+#[test]
+fn test_copy_text_sort() -> Result<(), anyhow::Error> {
+    let lines = common::sort_lines(
+        &[
+            "3\t\\N",
+            "1\ta\\\\b",
+            "4\ta\\tb",
+            "2\ta b",
+            "5\t\\N",
+        ],
+        vec![
+            Field::new(2, FieldType::String).with_encoding(FieldEncoding::CopyText),
+            Field::new(1, FieldType::Integer),
+        ],
+        Order::Asc,
+    )?;
+    assert_eq!(lines, vec!["4\ta\\tb", "2\ta b", "1\ta\\\\b", "3\t\\N", "5\t\\N"]);
+    Ok(())
+}
+
+#[test]
+fn test_copy_text_null_integer() -> Result<(), anyhow::Error> {
+    let lines = common::sort_lines(
+        &["a\t\\N", "b\t10", "c\t-2"],
+        vec![Field::new(2, FieldType::Integer).with_encoding(FieldEncoding::CopyText)],
+        Order::Desc,
+    )?;
+    assert_eq!(lines, vec!["a\t\\N", "b\t10", "c\t-2"]);
+    Ok(())
+}
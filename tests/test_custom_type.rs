@@ -0,0 +1,54 @@
+use anyhow::anyhow;
+use text_file_sort::custom_type::KeyExtractor;
+use text_file_sort::field::Field;
+use text_file_sort::field_type::FieldType;
+use text_file_sort::order::Order;
+use text_file_sort::parse_failure::ParseFailure;
+
+mod common;
+
+// ticket identifiers, for example ABC-123, ordered by project and then by number
+struct TicketId;
+
+impl KeyExtractor for TicketId {
+    type Key = (String, u64);
+
+    fn extract(&self, value: &str) -> Result<Self::Key, anyhow::Error> {
+        let (project, number) = value.split_once('-')
+            .ok_or_else(|| anyhow!("Invalid ticket: {}", value))?;
+        Ok((project.to_string(), number.parse()?))
+    }
+}
+
+#[test]
+fn test_custom_type() -> Result<(), anyhow::Error> {
+    let input = ["ABC-10\tx", "XYZ-2\ty", "ABC-9\tz", "unassigned\tw", "ABC-100\tv", "XYZ-1\tu"];
+    let field = Field::new(1, FieldType::String)
+        .with_custom_type(TicketId)
+        .with_parse_failure(ParseFailure::Last);
+    let lines = common::sort_lines(&input, vec![field.clone()], Order::Asc)?;
+    assert_eq!(
+        lines,
+        vec!["ABC-9\tz", "ABC-10\tx", "ABC-100\tv", "XYZ-1\tu", "XYZ-2\ty", "unassigned\tw"]
+    );
+    assert!(!common::check_lines(&input, vec![field], Order::Asc)?);
+    Ok(())
+}
+
+#[test]
+fn test_custom_type_merge_check() -> Result<(), anyhow::Error> {
+    let field = Field::new(1, FieldType::String)
+        .with_custom_type(TicketId)
+        .with_parse_failure(ParseFailure::Last);
+    let merged = common::merge_lines(
+        &[&["ABC-9\tz", "XYZ-2\ty", "unassigned\tw"], &["ABC-10\tx", "ABC-100\tv", "XYZ-1\tu"]],
+        vec![field.clone()],
+        Order::Asc,
+    )?;
+    let sorted = ["ABC-9\tz", "ABC-10\tx", "ABC-100\tv", "XYZ-1\tu", "XYZ-2\ty", "unassigned\tw"];
+    assert_eq!(merged, sorted);
+    assert!(common::check_lines(&sorted, vec![field.clone()], Order::Asc)?);
+    assert!(!common::check_lines(&["ABC-10\tx", "ABC-9\tz"], vec![field.clone()], Order::Asc)?);
+    assert!(!common::check_lines(&["unassigned\tw", "ABC-1\tx"], vec![field], Order::Asc)?);
+    Ok(())
+}
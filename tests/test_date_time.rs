@@ -0,0 +1,37 @@
+use text_file_sort::date_time_format::DateTimeFormat;
+use text_file_sort::field::Field;
+use text_file_sort::field_type::FieldType;
+use text_file_sort::order::Order;
+
+mod common;
+
+#[test]
+fn test_iso_8601_mixed_timezones() -> Result<(), anyhow::Error> {
+    let field = Field::new(2, FieldType::DateTime { format: DateTimeFormat::Iso8601, timezone: None });
+    let lines = common::sort_lines(
+        &[
+            "a\t2021-02-16T08:00:00+03:00",
+            "b\t2021-02-16 06:30:00Z",
+            "c\t2021-02-16T02:00:00-04:00",
+            "d\t2021-02-16T05:59:59",
+        ],
+        vec![field],
+        Order::Asc,
+    )?;
+    assert_eq!(lines, vec![
+        "a\t2021-02-16T08:00:00+03:00",
+        "d\t2021-02-16T05:59:59",
+        "c\t2021-02-16T02:00:00-04:00",
+        "b\t2021-02-16 06:30:00Z",
+    ]);
+    Ok(())
+}
+
+#[test]
+fn test_gtfs_date() -> Result<(), anyhow::Error> {
+    let format = DateTimeFormat::Pattern("%Y%m%d".to_string());
+    let field = Field::new(2, FieldType::DateTime { format, timezone: None });
+    let lines = common::sort_lines(&["a\t20210301", "b\t20201231", "c\t20210216"], vec![field], Order::Asc)?;
+    assert_eq!(lines, vec!["b\t20201231", "c\t20210216", "a\t20210301"]);
+    Ok(())
+}
@@ -0,0 +1,32 @@
+use text_file_sort::field::Field;
+use text_file_sort::field_type::FieldType;
+use text_file_sort::order::Order;
+
+mod common;
+
+#[test]
+fn test_big_integer() -> Result<(), anyhow::Error> {
+    let lines = common::sort_lines(
+        &["a\t123456789012345678901234567890", "b\t-99999999999999999999999", "c\t0009", "d\t9223372036854775808"],
+        vec![Field::new(2, FieldType::BigInteger)],
+        Order::Asc,
+    )?;
+    assert_eq!(lines, vec![
+        "b\t-99999999999999999999999",
+        "c\t0009",
+        "d\t9223372036854775808",
+        "a\t123456789012345678901234567890",
+    ]);
+    Ok(())
+}
+
+#[test]
+fn test_decimal_separator() -> Result<(), anyhow::Error> {
+    let lines = common::sort_lines(
+        &["a\t0,10000000000000001", "b\t0,1", "c\t-0,5", "d\t0,09"],
+        vec![Field::new(2, FieldType::Decimal).with_decimal_separator(',')],
+        Order::Asc,
+    )?;
+    assert_eq!(lines, vec!["c\t-0,5", "d\t0,09", "b\t0,1", "a\t0,10000000000000001"]);
+    Ok(())
+}
@@ -0,0 +1,101 @@
+use std::fs;
+use std::path::PathBuf;
+use text_file_sort::field::Field;
+use text_file_sort::field_type::FieldType;
+use text_file_sort::sort::Sort;
+
+mod common;
+
+fn primary_key_sort(input_files: Vec<PathBuf>) -> Sort {
+    let mut text_file_sort = Sort::new(input_files, common::temp_file_name("./target/results/"));
+    text_file_sort.add_field(Field::new(1, FieldType::Integer));
+    text_file_sort.add_field(Field::new(2, FieldType::String));
+    text_file_sort
+}
+
+#[test]
+fn test_check_duplicates() -> Result<(), anyhow::Error> {
+    common::setup();
+    let first = common::write_input(
+        &[
+            "# comment",
+            "1\ta\tfirst",
+            "1\tb\tfirst",
+            "2\ta\tfirst",
+            "2\ta\tsecond",
+            "2\ta\tthird",
+            "3\ta\tfirst",
+        ]
+    )?;
+    let second = common::write_input(
+        &[
+            "1\tb\tsecond",
+            "3\tb\tfirst",
+            "4\ta\tfirst",
+        ]
+    )?;
+    let text_file_sort = primary_key_sort(vec![first.clone(), second.clone()]);
+
+    let mut groups = Vec::new();
+    let count = text_file_sort.check_duplicates(
+        |duplicates| {
+            groups.push(
+                duplicates.iter()
+                    .map(|d| (d.path().to_path_buf(), d.line_number(), d.line().to_string()))
+                    .collect::<Vec<(PathBuf, usize, String)>>()
+            );
+            Ok(())
+        }
+    )?;
+    assert_eq!(count, 2);
+    assert_eq!(
+        groups,
+        vec![
+            vec![
+                (first.clone(), 3, "1\tb\tfirst".to_string()),
+                (second.clone(), 1, "1\tb\tsecond".to_string()),
+            ],
+            vec![
+                (first.clone(), 4, "2\ta\tfirst".to_string()),
+                (first.clone(), 5, "2\ta\tsecond".to_string()),
+                (first.clone(), 6, "2\ta\tthird".to_string()),
+            ],
+        ]
+    );
+
+    let report = common::temp_file_name("./target/results/");
+    assert_eq!(text_file_sort.report_duplicates(&report)?, 2);
+    let expected = vec![
+        format!("{}:3:1\tb\tfirst", first.display()),
+        format!("{}:1:1\tb\tsecond", second.display()),
+        "".to_string(),
+        format!("{}:4:2\ta\tfirst", first.display()),
+        format!("{}:5:2\ta\tsecond", first.display()),
+        format!("{}:6:2\ta\tthird", first.display()),
+    ];
+    assert_eq!(common::read_lines(report.clone())?, expected);
+    fs::remove_file(first)?;
+    fs::remove_file(second)?;
+    fs::remove_file(report)?;
+    Ok(())
+}
+
+#[test]
+fn test_check_no_duplicates() -> Result<(), anyhow::Error> {
+    common::setup();
+    let input = common::write_input(&["1\ta", "1\tb", "2\ta"])?;
+    let text_file_sort = primary_key_sort(vec![input.clone()]);
+    assert_eq!(text_file_sort.check_duplicates(|_| panic!("unexpected duplicates"))?, 0);
+    fs::remove_file(input)?;
+    Ok(())
+}
+
+#[test]
+fn test_check_duplicates_unsorted() -> Result<(), anyhow::Error> {
+    common::setup();
+    let input = common::write_input(&["2\ta", "1\ta", "1\ta"])?;
+    let text_file_sort = primary_key_sort(vec![input.clone()]);
+    assert!(text_file_sort.check_duplicates(|_| Ok(())).is_err());
+    fs::remove_file(input)?;
+    Ok(())
+}
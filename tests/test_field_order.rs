@@ -0,0 +1,55 @@
+use std::fs;
+use std::path::PathBuf;
+use text_file_sort::field::Field;
+use text_file_sort::field_type::FieldType;
+use text_file_sort::order::Order;
+use text_file_sort::sort::Sort;
+
+mod common;
+
+fn fields() -> Vec<Field> {
+    vec![
+        Field::new(1, FieldType::Integer),
+        Field::new(2, FieldType::Integer).with_order(Order::Desc),
+    ]
+}
+
+#[test]
+fn test_field_order_sort_merge_check() -> Result<(), anyhow::Error> {
+    common::setup();
+    let mut lines = Vec::new();
+    for way_id in (0..100).rev() {
+        for version in 1..4 {
+            lines.push(format!("{}\t{}", way_id, version));
+        }
+    }
+    let input_path = common::write_input(&lines)?;
+    let sorted_path = common::temp_file_name("./target/results/");
+
+    // small chunks, the chunks are merged by the field orders too
+    let mut text_file_sort = Sort::new(vec![input_path.clone()], sorted_path.clone());
+    text_file_sort.with_fields(fields());
+    text_file_sort.with_tasks(2);
+    text_file_sort.with_chunk_size_bytes(100);
+    text_file_sort.with_tmp_dir(PathBuf::from("./target/results/"));
+    text_file_sort.sort()?;
+
+    let sorted = common::read_lines(sorted_path.clone())?;
+    assert_eq!(sorted.len(), 300);
+    assert_eq!(sorted[0], "0\t3");
+    assert_eq!(sorted[1], "0\t2");
+    assert_eq!(sorted[2], "0\t1");
+    assert_eq!(sorted[299], "99\t1");
+
+    let sorted: Vec<&str> = sorted.iter().map(|line| line.as_str()).collect();
+    assert!(common::check_lines(&sorted, fields(), Order::Asc)?);
+    assert!(!common::check_lines(&sorted, fields(), Order::Desc)?);
+
+    let merged = common::merge_lines(&[&sorted, &sorted], fields(), Order::Asc)?;
+    assert_eq!(merged.len(), 600);
+    assert_eq!(merged[0..6], ["0\t3", "0\t3", "0\t2", "0\t2", "0\t1", "0\t1"]);
+
+    fs::remove_file(input_path)?;
+    fs::remove_file(sorted_path)?;
+    Ok(())
+}
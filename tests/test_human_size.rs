@@ -0,0 +1,25 @@
+use text_file_sort::field::Field;
+use text_file_sort::field_type::FieldType;
+use text_file_sort::order::Order;
+
+mod common;
+
+#[test]
+fn test_human_size() -> Result<(), anyhow::Error> {
+    let lines = common::sort_lines(
+        &[
+            "3G\t/a", "1.5K\t/b", "20M\t/c", "999\t/d", "1KiB\t/e", "1 kB\t/f", "0.5GiB\t/g", "-2M\t/h",
+            "1020\t/i", "1.01K\t/j", "2e3\t/k", "1E\t/l", "1e-3G\t/m",
+        ],
+        vec![Field::new(1, FieldType::HumanSize)],
+        Order::Asc,
+    )?;
+    assert_eq!(
+        lines,
+        vec![
+            "-2M\t/h", "999\t/d", "1 kB\t/f", "1020\t/i", "1KiB\t/e", "1.01K\t/j", "1.5K\t/b", "2e3\t/k",
+            "1e-3G\t/m", "20M\t/c", "0.5GiB\t/g", "3G\t/a", "1E\t/l",
+        ]
+    );
+    Ok(())
+}
@@ -0,0 +1,41 @@
+use regex::Regex;
+use text_file_sort::field::Field;
+use text_file_sort::field_type::FieldType;
+use text_file_sort::order::Order;
+
+mod common;
+
+#[test]
+fn test_dictionary_order() -> Result<(), anyhow::Error> {
+    let lines = common::sort_lines(
+        &["1\t\"Zion\"", "2\tO'Brien", "3\t(Berlin)", "4\tob rien", "5\tObrien"],
+        common::with_id(Field::new(2, FieldType::String).with_dictionary_order(true)),
+        Order::Asc,
+    )?;
+    assert_eq!(lines, vec!["3\t(Berlin)", "2\tO'Brien", "5\tObrien", "1\t\"Zion\"", "4\tob rien"]);
+    Ok(())
+}
+
+#[test]
+fn test_ignore_nonprinting() -> Result<(), anyhow::Error> {
+    let lines = common::sort_lines(
+        &["1\t\u{feff}Haifa", "2\tAcre", "3\tHai\u{200b}fa", "4\t\u{7}Eilat"],
+        common::with_id(Field::new(2, FieldType::String).with_ignore_nonprinting(true)),
+        Order::Asc,
+    )?;
+    assert_eq!(lines, vec!["2\tAcre", "4\t\u{7}Eilat", "1\t\u{feff}Haifa", "3\tHai\u{200b}fa"]);
+    Ok(())
+}
+
+#[test]
+fn test_ignore_chars() -> Result<(), anyhow::Error> {
+    let lines = common::sort_lines(
+        &["1\tst-louis", "2\tSt. Louis", "3\tSaint Louis", "4\tst louis"],
+        common::with_id(Field::new(2, FieldType::String)
+            .with_ignore_chars(Regex::new(r"[\p{P}\s]")?)
+            .with_case_folding(true)),
+        Order::Asc,
+    )?;
+    assert_eq!(lines, vec!["3\tSaint Louis", "1\tst-louis", "2\tSt. Louis", "4\tst louis"]);
+    Ok(())
+}
@@ -0,0 +1,61 @@
+use text_file_sort::field::Field;
+use text_file_sort::field_type::FieldType;
+use text_file_sort::order::Order;
+
+mod common;
+
+#[test]
+fn test_unsigned_integer() -> Result<(), anyhow::Error> {
+    let lines = common::sort_lines(
+        &["a\t18446744073709551615", "b\t9223372036854775808", "c\t1"],
+        vec![Field::new(2, FieldType::UnsignedInteger)],
+        Order::Asc,
+    )?;
+    assert_eq!(lines, vec!["c\t1", "b\t9223372036854775808", "a\t18446744073709551615"]);
+    Ok(())
+}
+
+#[test]
+fn test_integer_128() -> Result<(), anyhow::Error> {
+    let lines = common::sort_lines(
+        &["a\t170141183460469231731687303715884105727", "b\t-170141183460469231731687303715884105728", "c\t0"],
+        vec![Field::new(2, FieldType::Integer128)],
+        Order::Asc,
+    )?;
+    assert_eq!(lines, vec![
+        "b\t-170141183460469231731687303715884105728",
+        "c\t0",
+        "a\t170141183460469231731687303715884105727",
+    ]);
+
+    let lines = common::sort_lines(
+        &["a\t340282366920938463463374607431768211455", "b\t18446744073709551616"],
+        vec![Field::new(2, FieldType::UnsignedInteger128)],
+        Order::Asc,
+    )?;
+    assert_eq!(lines, vec!["b\t18446744073709551616", "a\t340282366920938463463374607431768211455"]);
+    Ok(())
+}
+
+#[test]
+fn test_hex_integer() -> Result<(), anyhow::Error> {
+    let lines = common::sort_lines(
+        &[
+            "a\t0x1f3a",
+            "b\tff",
+            "c\t0X00F0",
+            "d\tda39a3ee5e6b4b0d3255bfef95601890afd80709",
+            "e\t0000000000000000000000000000000000000000000000001",
+        ],
+        vec![Field::new(2, FieldType::HexInteger)],
+        Order::Asc,
+    )?;
+    assert_eq!(lines, vec![
+        "e\t0000000000000000000000000000000000000000000000001",
+        "c\t0X00F0",
+        "b\tff",
+        "a\t0x1f3a",
+        "d\tda39a3ee5e6b4b0d3255bfef95601890afd80709",
+    ]);
+    Ok(())
+}
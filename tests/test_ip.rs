@@ -0,0 +1,53 @@
+use text_file_sort::field::Field;
+use text_file_sort::field_type::FieldType;
+use text_file_sort::order::Order;
+use text_file_sort::parse_failure::ParseFailure;
+
+mod common;
+
+#[test]
+fn test_ip_addr() -> Result<(), anyhow::Error> {
+    let lines = common::sort_lines(
+        &["GET\t10.0.0.10", "GET\t::1", "GET\t10.0.0.9", "GET\t-", "GET\t2001:db8::1", "GET\t9.255.255.255"],
+        vec![Field::new(2, FieldType::IpAddr).with_parse_failure(ParseFailure::Last)],
+        Order::Asc,
+    )?;
+    assert_eq!(lines, vec![
+        "GET\t9.255.255.255",
+        "GET\t10.0.0.9",
+        "GET\t10.0.0.10",
+        "GET\t::1",
+        "GET\t2001:db8::1",
+        "GET\t-",
+    ]);
+    Ok(())
+}
+
+#[test]
+fn test_ip_addr_invalid_first_desc() -> Result<(), anyhow::Error> {
+    let lines = common::sort_lines(
+        &["a\t10.0.0.10", "b\tunknown", "c\t10.0.0.9", "d\t-"],
+        vec![Field::new(2, FieldType::IpAddr).with_parse_failure(ParseFailure::First)],
+        Order::Desc,
+    )?;
+    assert_eq!(lines, vec!["d\t-", "b\tunknown", "a\t10.0.0.10", "c\t10.0.0.9"]);
+    Ok(())
+}
+
+#[test]
+fn test_ip_network() -> Result<(), anyhow::Error> {
+    let lines = common::sort_lines(
+        &["a\t10.0.0.0/16", "b\t10.0.0.0/8", "c\t10.0.1.7/24", "d\t10.0.0.1", "e\t192.168.0.0/16", "f\t2001:db8::/32"],
+        vec![Field::new(2, FieldType::IpNetwork)],
+        Order::Asc,
+    )?;
+    assert_eq!(lines, vec![
+        "b\t10.0.0.0/8",
+        "a\t10.0.0.0/16",
+        "d\t10.0.0.1",
+        "c\t10.0.1.7/24",
+        "e\t192.168.0.0/16",
+        "f\t2001:db8::/32",
+    ]);
+    Ok(())
+}
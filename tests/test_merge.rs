@@ -28,3 +28,25 @@ fn test_merge() -> Result<(), anyhow::Error> {
     fs::remove_file(output_path)?;
     Ok(())
 }
+
+#[test]
+fn test_merge_keeps_input_files() -> Result<(), anyhow::Error> {
+    common::setup();
+    let first = common::write_input(&["a", "c"])?;
+    let second = common::write_input(&["b", "d"])?;
+    for input_files in [vec![first.clone()], vec![first.clone(), second.clone()]] {
+        let output_path = common::temp_file_name("./target/results/");
+        let mut text_file_sort = Sort::new(input_files.clone(), output_path.clone());
+        text_file_sort.with_tmp_dir(PathBuf::from("./target/tmp"));
+        text_file_sort.merge()?;
+        for input_file in input_files {
+            assert!(input_file.exists(), "merge removed {}", input_file.display());
+        }
+        fs::remove_file(output_path)?;
+    }
+    assert_eq!(common::read_lines(first.clone())?, vec!["a", "c"]);
+    assert_eq!(common::read_lines(second.clone())?, vec!["b", "d"]);
+    fs::remove_file(first)?;
+    fs::remove_file(second)?;
+    Ok(())
+}
@@ -0,0 +1,27 @@
+use text_file_sort::field::Field;
+use text_file_sort::field_type::FieldType;
+use text_file_sort::order::Order;
+
+mod common;
+
+#[test]
+fn test_month() -> Result<(), anyhow::Error> {
+    let lines = common::sort_lines(
+        &["1\tDec", "2\tjanuary", "3\tSept", "4\tFEB", "5\t???", "6\tAug", "7\tJa", "8\tJanxyz"],
+        common::with_id(Field::new(2, FieldType::Month)),
+        Order::Asc,
+    )?;
+    assert_eq!(lines, vec!["5\t???", "7\tJa", "2\tjanuary", "8\tJanxyz", "4\tFEB", "6\tAug", "3\tSept", "1\tDec"]);
+    Ok(())
+}
+
+#[test]
+fn test_weekday() -> Result<(), anyhow::Error> {
+    let lines = common::sort_lines(
+        &["1\tSun", "2\tThursday", "3\ttues", "4\tMon", "5\tholiday", "6\tSAT"],
+        common::with_id(Field::new(2, FieldType::Weekday)),
+        Order::Asc,
+    )?;
+    assert_eq!(lines, vec!["5\tholiday", "4\tMon", "3\ttues", "2\tThursday", "6\tSAT", "1\tSun"]);
+    Ok(())
+}
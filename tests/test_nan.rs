@@ -0,0 +1,73 @@
+use text_file_sort::field::Field;
+use text_file_sort::field_type::FieldType;
+use text_file_sort::nan::Nan;
+use text_file_sort::order::Order;
+use text_file_sort::spatial::Curve;
+
+mod common;
+
+const INPUT: [&str; 8] = ["1\tNaN", "2\tinf", "3\t-0.0", "4\t-Infinity", "5\t0", "6\t+infinity", "7\tnan", "8\t-1e308"];
+
+#[test]
+fn test_infinity() -> Result<(), anyhow::Error> {
+    let ids = common::sort_ids(&INPUT, common::with_id(Field::new(2, FieldType::Number)), Order::Asc)?;
+    assert_eq!(ids, vec!["1", "7", "4", "8", "3", "5", "2", "6"]);
+    let ids = common::sort_ids(&INPUT, common::with_id(Field::new(2, FieldType::Number)), Order::Desc)?;
+    assert_eq!(ids, vec!["2", "6", "3", "5", "8", "4", "1", "7"]);
+    Ok(())
+}
+
+#[test]
+fn test_nan() -> Result<(), anyhow::Error> {
+    let ids = common::sort_ids(&INPUT, common::with_id(Field::new(2, FieldType::Number).with_nan(Nan::Last)), Order::Asc)?;
+    assert_eq!(ids, vec!["4", "8", "3", "5", "2", "6", "1", "7"]);
+    let ids = common::sort_ids(&INPUT, common::with_id(Field::new(2, FieldType::Number).with_nan(Nan::Last)), Order::Desc)?;
+    assert_eq!(ids, vec!["2", "6", "3", "5", "8", "4", "1", "7"]);
+    let ids = common::sort_ids(&INPUT, common::with_id(Field::new(2, FieldType::Number).with_nan(Nan::First)), Order::Desc)?;
+    assert_eq!(ids, vec!["1", "7", "2", "6", "3", "5", "8", "4"]);
+    Ok(())
+}
+
+#[test]
+fn test_signed_zero() -> Result<(), anyhow::Error> {
+    let ids = common::sort_ids(&INPUT, common::with_id(Field::new(2, FieldType::Number).with_signed_zero(true)), Order::Desc)?;
+    assert_eq!(ids, vec!["2", "6", "5", "3", "8", "4", "1", "7"]);
+    Ok(())
+}
+
+#[test]
+fn test_nan_with_nulls_desc() -> Result<(), anyhow::Error> {
+    let input = ["1\t\\N", "2\tNaN", "3\t1.5", "4\t-2", "5\t\\N", "6\tnan"];
+    let field = Field::new(2, FieldType::Number).with_null_tokens(vec!["\\N".to_string()]);
+
+    // NULL without a position is first in descending order, also before NaN first
+    let ids = common::sort_ids(&input, common::with_id(field.clone().with_nan(Nan::Last)), Order::Desc)?;
+    assert_eq!(ids, vec!["1", "5", "3", "4", "2", "6"]);
+    let ids = common::sort_ids(&input, common::with_id(field.clone().with_nan(Nan::First)), Order::Desc)?;
+    assert_eq!(ids, vec!["1", "5", "2", "6", "3", "4"]);
+
+    let field = field.with_nan(Nan::Last);
+    let merged = common::merge_lines(
+        &[&["1\t\\N", "3\t1.5", "2\tNaN"], &["5\t\\N", "4\t-2", "6\tnan"]],
+        common::with_id(field.clone()),
+        Order::Desc,
+    )?;
+    assert_eq!(merged, vec!["1\t\\N", "5\t\\N", "3\t1.5", "4\t-2", "2\tNaN", "6\tnan"]);
+    assert!(!common::check_lines(&["2\tNaN", "1\t\\N"], common::with_id(field.clone()), Order::Desc)?);
+    assert!(!common::check_lines(&["3\t1.5", "1\t\\N"], common::with_id(field), Order::Desc)?);
+    Ok(())
+}
+
+#[test]
+fn test_spatial_nan() -> Result<(), anyhow::Error> {
+    let field = Field::new_spatial(
+        Field::new(2, FieldType::Number).with_nan(Nan::Last),
+        Field::new(3, FieldType::Number).with_nan(Nan::Last),
+        Curve::Hilbert,
+        16,
+    );
+    let input = ["1\tNaN\t34.78", "2\t32.08\t34.78", "3\t32.09\tnan", "4\t32.07\t34.77"];
+    let ids = common::sort_ids(&input, common::with_id(field), Order::Asc)?;
+    assert_eq!(&ids[2..], &["1", "3"]);
+    Ok(())
+}
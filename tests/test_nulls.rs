@@ -0,0 +1,51 @@
+use text_file_sort::field::Field;
+use text_file_sort::field_type::FieldType;
+use text_file_sort::nulls::Nulls;
+use text_file_sort::order::Order;
+
+mod common;
+
+const INPUT: [&str; 5] = ["a\t2", "b\t", "c\tNULL", "d\t1", "e\t\\N"];
+
+// the field with the key followed by the name in the first field
+fn fields(field: Field) -> Vec<Field> {
+    vec![field, Field::new(1, FieldType::String)]
+}
+
+fn null_tokens() -> Vec<String> {
+    vec!["\\N".to_string(), "".to_string(), "NULL".to_string()]
+}
+
+#[test]
+fn test_null_tokens_default_position() -> Result<(), anyhow::Error> {
+    let field = Field::new(2, FieldType::Integer).with_null_tokens(null_tokens());
+    let lines = common::sort_lines(&INPUT, fields(field.clone()), Order::Asc)?;
+    assert_eq!(lines, vec!["d\t1", "a\t2", "b\t", "c\tNULL", "e\t\\N"]);
+    let lines = common::sort_lines(&INPUT, fields(field), Order::Desc)?;
+    assert_eq!(lines, vec!["e\t\\N", "c\tNULL", "b\t", "a\t2", "d\t1"]);
+    Ok(())
+}
+
+#[test]
+fn test_nulls_first() -> Result<(), anyhow::Error> {
+    let field = Field::new(2, FieldType::Integer)
+        .with_null_tokens(null_tokens())
+        .with_nulls(Nulls::First);
+    let lines = common::sort_lines(&INPUT, fields(field.clone()), Order::Asc)?;
+    assert_eq!(lines, vec!["b\t", "c\tNULL", "e\t\\N", "d\t1", "a\t2"]);
+    let lines = common::sort_lines(&INPUT, fields(field), Order::Desc)?;
+    assert_eq!(lines, vec!["e\t\\N", "c\tNULL", "b\t", "a\t2", "d\t1"]);
+    Ok(())
+}
+
+#[test]
+fn test_nulls_last() -> Result<(), anyhow::Error> {
+    let field = Field::new(2, FieldType::Integer)
+        .with_null_tokens(null_tokens())
+        .with_nulls(Nulls::Last);
+    let lines = common::sort_lines(&INPUT, fields(field.clone()), Order::Asc)?;
+    assert_eq!(lines, vec!["d\t1", "a\t2", "b\t", "c\tNULL", "e\t\\N"]);
+    let lines = common::sort_lines(&INPUT, fields(field), Order::Desc)?;
+    assert_eq!(lines, vec!["a\t2", "d\t1", "e\t\\N", "c\tNULL", "b\t"]);
+    Ok(())
+}
@@ -0,0 +1,79 @@
+use text_file_sort::field::Field;
+use text_file_sort::field_type::FieldType;
+use text_file_sort::number_format::NumberFormat;
+use text_file_sort::order::Order;
+use text_file_sort::parse_failure::ParseFailure;
+
+mod common;
+
+#[test]
+fn test_european_number_format() -> Result<(), anyhow::Error> {
+    let european = NumberFormat::new()
+        .with_decimal_separator(',')
+        .with_grouping_separator('.')
+        .with_currency_symbols(vec!["€".to_string()]);
+    let input = ["a;1.234,56 €", "b;-12,5 €", "c;999,99 €", "d;1.000.000 €"];
+    let field = Field::new(2, FieldType::Number).with_number_format(european.clone());
+    let lines = common::sort_separated_lines(&input, vec![field], Order::Asc, ';')?;
+    assert_eq!(lines, vec!["b;-12,5 €", "c;999,99 €", "a;1.234,56 €", "d;1.000.000 €"]);
+
+    let field = Field::new(2, FieldType::Decimal).with_number_format(european);
+    let lines = common::sort_separated_lines(&input, vec![field], Order::Asc, ';')?;
+    assert_eq!(lines, vec!["b;-12,5 €", "c;999,99 €", "a;1.234,56 €", "d;1.000.000 €"]);
+    Ok(())
+}
+
+#[test]
+fn test_accounting_number_format() -> Result<(), anyhow::Error> {
+    let accounting = NumberFormat::new()
+        .with_grouping_separator(',')
+        .with_currency_symbols(vec!["$".to_string()])
+        .with_parenthesized_negatives(true);
+    let lines = common::sort_separated_lines(
+        &["a;$1,200", "b;($1,234)", "c;($5)", "d;$80"],
+        vec![Field::new(2, FieldType::Integer).with_number_format(accounting)],
+        Order::Asc,
+        ';',
+    )?;
+    assert_eq!(lines, vec!["b;($1,234)", "c;($5)", "d;$80", "a;$1,200"]);
+    Ok(())
+}
+
+#[test]
+fn test_number_format_parse_failure_prefix() -> Result<(), anyhow::Error> {
+    let european = NumberFormat::new()
+        .with_decimal_separator(',')
+        .with_grouping_separator('.')
+        .with_currency_symbols(vec!["€".to_string()]);
+    let lines = common::sort_separated_lines(
+        &["a;1.234,5x", "b;999 €x", "c;1.234,4 €", "d;n/a"],
+        vec![Field::new(2, FieldType::Number).with_number_format(european).with_parse_failure(ParseFailure::Prefix)],
+        Order::Asc,
+        ';',
+    )?;
+    assert_eq!(lines, vec!["d;n/a", "b;999 €x", "c;1.234,4 €", "a;1.234,5x"]);
+
+    let accounting = NumberFormat::new()
+        .with_grouping_separator(',')
+        .with_currency_symbols(vec!["$".to_string()])
+        .with_parenthesized_negatives(true);
+    let lines = common::sort_separated_lines(
+        &["a;$1,200 approx", "b;($1,234)", "c;$5?"],
+        vec![Field::new(2, FieldType::Integer).with_number_format(accounting).with_parse_failure(ParseFailure::Prefix)],
+        Order::Asc,
+        ';',
+    )?;
+    assert_eq!(lines, vec!["b;($1,234)", "c;$5?", "a;$1,200 approx"]);
+    Ok(())
+}
+
+#[test]
+fn test_decimal_comma_rejects_period() -> Result<(), anyhow::Error> {
+    // '.' is neither the decimal nor the grouping separator, so 1.234 is an invalid value
+    let field = Field::new(2, FieldType::Number)
+        .with_decimal_separator(',')
+        .with_parse_failure(ParseFailure::Last);
+    let lines = common::sort_separated_lines(&["a;1.234", "b;2,5", "c;1,5"], vec![field], Order::Asc, ';')?;
+    assert_eq!(lines, vec!["c;1,5", "b;2,5", "a;1.234"]);
+    Ok(())
+}
@@ -0,0 +1,105 @@
+use text_file_sort::field::Field;
+use text_file_sort::field_type::FieldType;
+use text_file_sort::nulls::Nulls;
+use text_file_sort::order::Order;
+use text_file_sort::parse_failure::ParseFailure;
+
+mod common;
+
+const INPUT: [&str; 6] = ["1\t12abc", "2\tn/a", "3\t-3.5e2", "4\t7", "5\t", "6\t-1"];
+
+#[test]
+fn test_parse_failure_null() -> Result<(), anyhow::Error> {
+    let field = Field::new(2, FieldType::Integer)
+        .with_parse_failure(ParseFailure::Null)
+        .with_nulls(Nulls::First);
+    assert_eq!(common::sort_ids(&INPUT, common::with_id(field), Order::Asc)?, vec!["1", "2", "3", "5", "6", "4"]);
+    Ok(())
+}
+
+#[test]
+fn test_parse_failure_zero() -> Result<(), anyhow::Error> {
+    let field = Field::new(2, FieldType::Integer).with_parse_failure(ParseFailure::Zero);
+    assert_eq!(common::sort_ids(&INPUT, common::with_id(field), Order::Asc)?, vec!["6", "1", "2", "3", "5", "4"]);
+    Ok(())
+}
+
+#[test]
+fn test_parse_failure_prefix() -> Result<(), anyhow::Error> {
+    let field = Field::new(2, FieldType::Integer).with_parse_failure(ParseFailure::Prefix);
+    assert_eq!(common::sort_ids(&INPUT, common::with_id(field), Order::Asc)?, vec!["3", "6", "2", "5", "4", "1"]);
+
+    let field = Field::new(2, FieldType::Number).with_parse_failure(ParseFailure::Prefix);
+    assert_eq!(common::sort_ids(&INPUT, common::with_id(field), Order::Desc)?, vec!["1", "4", "2", "5", "6", "3"]);
+    Ok(())
+}
+
+#[test]
+fn test_parse_failure_last() -> Result<(), anyhow::Error> {
+    let field = Field::new(2, FieldType::Number).with_parse_failure(ParseFailure::Last);
+    assert_eq!(common::sort_ids(&INPUT, common::with_id(field.clone()), Order::Asc)?, vec!["3", "6", "4", "5", "1", "2"]);
+    assert_eq!(common::sort_ids(&INPUT, common::with_id(field), Order::Desc)?, vec!["4", "6", "3", "5", "1", "2"]);
+    Ok(())
+}
+
+#[test]
+fn test_parse_failure_with_nulls_desc() -> Result<(), anyhow::Error> {
+    // NULL without a position is first in descending order, invalid values are last
+    let field = Field::new(2, FieldType::Integer)
+        .with_null_tokens(vec!["\\N".to_string()])
+        .with_parse_failure(ParseFailure::Last);
+    let input = ["1\t\\N", "2\t5", "3\tx", "4\t7", "5\t\\N", "6\ty"];
+    let sorted = vec!["1\t\\N", "5\t\\N", "4\t7", "2\t5", "3\tx", "6\ty"];
+    assert_eq!(common::sort_lines(&input, common::with_id(field.clone()), Order::Desc)?, sorted);
+
+    let merged = common::merge_lines(
+        &[&["1\t\\N", "4\t7", "3\tx"], &["5\t\\N", "2\t5", "6\ty"]],
+        common::with_id(field.clone()),
+        Order::Desc,
+    )?;
+    assert_eq!(merged, sorted);
+
+    assert!(!common::check_lines(&["39\t\\N", "38\t38", "37\tx37", "36\t\\N"], common::with_id(field.clone()), Order::Desc)?);
+    assert!(!common::check_lines(&["3\tx", "1\t\\N"], common::with_id(field), Order::Desc)?);
+    Ok(())
+}
+
+#[test]
+fn test_parse_failure_merge_check() -> Result<(), anyhow::Error> {
+    let policies = [
+        (ParseFailure::Null, FieldType::Integer, ["1", "2", "3", "5", "6", "4"]),
+        (ParseFailure::Zero, FieldType::Integer, ["6", "1", "2", "3", "5", "4"]),
+        (ParseFailure::Prefix, FieldType::Integer, ["3", "6", "2", "5", "4", "1"]),
+        (ParseFailure::Last, FieldType::Number, ["3", "6", "4", "5", "1", "2"]),
+    ];
+    for (parse_failure, field_type, ids) in policies {
+        let field = Field::new(2, field_type)
+            .with_parse_failure(parse_failure)
+            .with_nulls(Nulls::First);
+        let sorted: Vec<&str> = ids.iter()
+            .map(|id| *INPUT.iter().find(|line| line.starts_with(&format!("{}\t", id))).unwrap())
+            .collect();
+        let first: Vec<&str> = sorted.iter().step_by(2).copied().collect();
+        let second: Vec<&str> = sorted.iter().skip(1).step_by(2).copied().collect();
+        let merged = common::merge_lines(&[&first, &second], common::with_id(field.clone()), Order::Asc)?;
+        assert_eq!(merged, sorted);
+
+        assert!(common::check_lines(&sorted, common::with_id(field.clone()), Order::Asc)?);
+        let reversed: Vec<&str> = sorted.iter().rev().copied().collect();
+        assert!(!common::check_lines(&reversed, common::with_id(field), Order::Asc)?);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_parse_failure_fail_merge() -> Result<(), anyhow::Error> {
+    let field = Field::new(1, FieldType::Integer);
+    assert!(common::merge_lines(&[&["1", "x", "5", "7"], &["2", "3"]], vec![field.clone()], Order::Asc).is_err());
+    assert!(common::merge_lines(&[&["1", "x", "5"]], vec![field.clone()], Order::Asc).is_err());
+    assert!(common::check_lines(&["1", "x", "5"], vec![field], Order::Asc).is_err());
+
+    let field = Field::new(1, FieldType::Integer).with_parse_failure(ParseFailure::Null);
+    let merged = common::merge_lines(&[&["1", "5", "7", "x"], &["2", "3"]], vec![field], Order::Asc)?;
+    assert_eq!(merged, vec!["1", "2", "3", "5", "7", "x"]);
+    Ok(())
+}
@@ -0,0 +1,33 @@
+use std::path::Path;
+use text_file_sort::field::Field;
+use text_file_sort::field_type::FieldType;
+use text_file_sort::order::Order;
+use text_file_sort::parse_failure::ParseFailure;
+
+mod common;
+
+#[test]
+fn test_ranked() -> Result<(), anyhow::Error> {
+    let ranked = FieldType::Ranked(vec!["critical".to_string(), "high".to_string(), "medium".to_string(), "low".to_string()]);
+    let lines = common::sort_lines(
+        &["1\tlow", "2\tHigh", "3\tcritical", "4\tunknown", "5\tmedium", "6\thigh"],
+        common::with_id(Field::new(2, ranked)
+            .with_ignore_case(true)
+            .with_parse_failure(ParseFailure::First)),
+        Order::Asc,
+    )?;
+    assert_eq!(lines, vec!["4\tunknown", "3\tcritical", "2\tHigh", "6\thigh", "5\tmedium", "1\tlow"]);
+    Ok(())
+}
+
+#[test]
+fn test_ranked_from_file() -> Result<(), anyhow::Error> {
+    let ranked = FieldType::ranked_from_file(Path::new("./tests/fixtures/severity-rank.dat"))?;
+    let lines = common::sort_lines(
+        &["1\tlow", "2\tinfo", "3\tcritical", "4\tmedium"],
+        common::with_id(Field::new(2, ranked).with_parse_failure(ParseFailure::Last)),
+        Order::Asc,
+    )?;
+    assert_eq!(lines, vec!["3\tcritical", "4\tmedium", "1\tlow", "2\tinfo"]);
+    Ok(())
+}
@@ -0,0 +1,45 @@
+use text_file_sort::field::Field;
+use text_file_sort::field_type::FieldType;
+use text_file_sort::order::Order;
+use text_file_sort::spatial::Curve;
+
+mod common;
+
+fn sort_nodes(curve: Curve) -> Result<Vec<String>, anyhow::Error> {
+    // node id, latitude, longitude: two clusters, around Tel Aviv and around New York
+    common::sort_ids(
+        &[
+            "1\t32.08\t34.78",
+            "2\t40.71\t-74.00",
+            "3\t32.09\t34.79",
+            "4\t40.72\t-74.01",
+            "5\t32.07\t34.77",
+            "6\t40.70\t-73.99",
+        ],
+        vec![
+            Field::new_spatial(
+                Field::new(2, FieldType::Number),
+                Field::new(3, FieldType::Number),
+                curve,
+                16,
+            )
+        ],
+        Order::Asc,
+    )
+}
+
+#[test]
+fn test_spatial_locality() -> Result<(), anyhow::Error> {
+    for curve in [Curve::Hilbert, Curve::ZOrder] {
+        let ids = sort_nodes(curve)?;
+        assert_eq!(ids.len(), 6);
+        let mut first: Vec<&str> = ids[0..3].iter().map(|id| id.as_str()).collect();
+        let mut second: Vec<&str> = ids[3..6].iter().map(|id| id.as_str()).collect();
+        first.sort();
+        second.sort();
+        // the west cluster precedes the east cluster on both curves
+        assert_eq!(first, vec!["2", "4", "6"]);
+        assert_eq!(second, vec!["1", "3", "5"]);
+    }
+    Ok(())
+}
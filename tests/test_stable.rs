@@ -0,0 +1,106 @@
+use std::fs;
+use std::path::PathBuf;
+use text_file_sort::field::Field;
+use text_file_sort::field_type::FieldType;
+use text_file_sort::order::Order;
+use text_file_sort::sort::Sort;
+
+mod common;
+
+fn stable_sort(input_files: Vec<PathBuf>, tasks: usize, chunk_size_bytes: u64, order: Order) -> Result<Vec<String>, anyhow::Error> {
+    let output_path = common::temp_file_name("./target/results/");
+    let mut text_file_sort = Sort::new(input_files, output_path.clone());
+    text_file_sort.add_field(Field::new(1, FieldType::Integer));
+    text_file_sort.with_order(order);
+    text_file_sort.with_stable(true);
+    text_file_sort.with_tasks(tasks);
+    text_file_sort.with_chunk_size_bytes(chunk_size_bytes);
+    text_file_sort.with_intermediate_files(2);
+    text_file_sort.with_tmp_dir(PathBuf::from("./target/results/"));
+    text_file_sort.sort()?;
+    let lines = common::read_lines(output_path.clone())?;
+    fs::remove_file(output_path)?;
+    Ok(lines)
+}
+
+#[test]
+fn test_stable_sort() -> Result<(), anyhow::Error> {
+    common::setup();
+    let first = common::keyed_lines(2000, 5, 1);
+    let second = common::keyed_lines(1000, 5, 2);
+    let input_files = vec![common::write_input(&first)?, common::write_input(&second)?];
+
+    let mut expected: Vec<String> = first.iter().chain(second.iter()).cloned().collect();
+    expected.sort_by_key(|line| line.split('\t').next().unwrap().parse::<i64>().unwrap());
+    for (tasks, chunk_size_bytes) in [(1, 10_000_000), (4, 1000), (3, 257)] {
+        let lines = stable_sort(input_files.clone(), tasks, chunk_size_bytes, Order::Asc)?;
+        assert_eq!(lines, expected, "tasks: {}, chunk size: {}", tasks, chunk_size_bytes);
+    }
+
+    expected.sort_by_key(|line| -line.split('\t').next().unwrap().parse::<i64>().unwrap());
+    let lines = stable_sort(input_files.clone(), 4, 1000, Order::Desc)?;
+    assert_eq!(lines, expected);
+
+    for path in input_files {
+        fs::remove_file(path)?;
+    }
+    Ok(())
+}
+
+#[test]
+fn test_stable_merge() -> Result<(), anyhow::Error> {
+    common::setup();
+    let mut first = common::keyed_lines(100, 5, 1);
+    let mut second = common::keyed_lines(100, 5, 2);
+    let key = |line: &String| line.split('\t').next().unwrap().parse::<i64>().unwrap();
+    first.sort_by_key(key);
+    second.sort_by_key(key);
+    let input_files = vec![common::write_input(&second)?, common::write_input(&first)?];
+
+    let output_path = common::temp_file_name("./target/results/");
+    let mut text_file_sort = Sort::new(input_files.clone(), output_path.clone());
+    text_file_sort.add_field(Field::new(1, FieldType::Integer));
+    text_file_sort.with_stable(true);
+    text_file_sort.with_tmp_dir(PathBuf::from("./target/results/"));
+    text_file_sort.merge()?;
+
+    let mut expected: Vec<String> = second.iter().chain(first.iter()).cloned().collect();
+    expected.sort_by_key(key);
+    assert_eq!(common::read_lines(output_path.clone())?, expected);
+
+    fs::remove_file(output_path)?;
+    for path in input_files {
+        fs::remove_file(path)?;
+    }
+    Ok(())
+}
+
+#[test]
+fn test_last_resort() -> Result<(), anyhow::Error> {
+    common::setup();
+    let input_path = common::write_input(&["1\tb".to_string(), "0\tz".to_string(), "1\ta".to_string(), "0\ta".to_string()])?;
+    let output_path = common::temp_file_name("./target/results/");
+
+    let mut text_file_sort = Sort::new(vec![input_path.clone()], output_path.clone());
+    text_file_sort.add_field(Field::new(1, FieldType::Integer));
+    text_file_sort.with_last_resort(true);
+    text_file_sort.with_order(Order::Desc);
+    text_file_sort.with_tmp_dir(PathBuf::from("./target/results/"));
+    text_file_sort.sort()?;
+    assert_eq!(common::read_lines(output_path.clone())?, vec!["1\tb", "1\ta", "0\tz", "0\ta"]);
+
+    let mut check = Sort::new(vec![output_path.clone()], PathBuf::new());
+    check.add_field(Field::new(1, FieldType::Integer));
+    check.with_last_resort(true);
+    check.with_order(Order::Desc);
+    assert!(check.check()?);
+
+    let mut check = Sort::new(vec![output_path.clone()], PathBuf::new());
+    check.add_field(Field::new(1, FieldType::Integer));
+    check.with_last_resort(true);
+    assert!(!check.check()?);
+
+    fs::remove_file(input_path)?;
+    fs::remove_file(output_path)?;
+    Ok(())
+}
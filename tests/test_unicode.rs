@@ -0,0 +1,41 @@
+use text_file_sort::field::Field;
+use text_file_sort::field_type::FieldType;
+use text_file_sort::normalization::Normalization;
+use text_file_sort::order::Order;
+
+mod common;
+
+#[test]
+fn test_normalization() -> Result<(), anyhow::Error> {
+    // the same name written precomposed and decomposed
+    let input = ["3\tCafe\u{301}", "1\tCafeteria", "2\tCaf\u{e9}"];
+    let lines = common::sort_lines(&input, common::with_id(Field::new(2, FieldType::String)), Order::Asc)?;
+    assert_eq!(lines, vec!["1\tCafeteria", "3\tCafe\u{301}", "2\tCaf\u{e9}"]);
+
+    let field = Field::new(2, FieldType::String).with_normalization(Normalization::Nfc);
+    let lines = common::sort_lines(&input, common::with_id(field), Order::Asc)?;
+    assert_eq!(lines, vec!["1\tCafeteria", "2\tCaf\u{e9}", "3\tCafe\u{301}"]);
+    Ok(())
+}
+
+#[test]
+fn test_case_folding() -> Result<(), anyhow::Error> {
+    let input = ["4\tstrasse", "3\tSTRASSE", "2\tStra\u{df}e", "1\tstrassf"];
+    let lines = common::sort_lines(&input, common::with_id(Field::new(2, FieldType::String).with_case_folding(true)), Order::Asc)?;
+    assert_eq!(lines, vec!["2\tStra\u{df}e", "3\tSTRASSE", "4\tstrasse", "1\tstrassf"]);
+    Ok(())
+}
+
+#[test]
+fn test_ignore_accents() -> Result<(), anyhow::Error> {
+    let input = ["3\tZ\u{fc}rich", "2\tZurich", "1\tZ\u{fc}rich", "4\tZug"];
+    let lines = common::sort_lines(
+        &input,
+        common::with_id(Field::new(2, FieldType::String)
+            .with_ignore_accents(true)
+            .with_case_folding(true)),
+        Order::Asc,
+    )?;
+    assert_eq!(lines, vec!["4\tZug", "1\tZ\u{fc}rich", "2\tZurich", "3\tZ\u{fc}rich"]);
+    Ok(())
+}
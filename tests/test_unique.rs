@@ -0,0 +1,102 @@
+use std::fs;
+use std::path::PathBuf;
+use text_file_sort::field::Field;
+use text_file_sort::field_type::FieldType;
+use text_file_sort::order::Order;
+use text_file_sort::sort::Sort;
+use text_file_sort::unique::Unique;
+
+mod common;
+
+fn key(line: &str) -> i64 {
+    line.split('\t').next().unwrap().parse::<i64>().unwrap()
+}
+
+fn unique_sort(input_files: Vec<PathBuf>, unique: Unique, order: Order) -> Result<Vec<String>, anyhow::Error> {
+    let output_path = common::temp_file_name("./target/results/");
+    let mut text_file_sort = Sort::new(input_files, output_path.clone());
+    text_file_sort.add_field(Field::new(1, FieldType::Integer));
+    text_file_sort.with_order(order.clone());
+    text_file_sort.with_unique(unique.clone());
+    text_file_sort.with_tasks(4);
+    text_file_sort.with_chunk_size_bytes(1000);
+    text_file_sort.with_intermediate_files(2);
+    text_file_sort.with_tmp_dir(PathBuf::from("./target/results/"));
+    text_file_sort.sort()?;
+    let lines = common::read_lines(output_path.clone())?;
+
+    let mut check = Sort::new(vec![output_path.clone()], PathBuf::new());
+    check.add_field(Field::new(1, FieldType::Integer));
+    check.with_order(order);
+    check.with_unique(unique);
+    assert!(check.check()?);
+
+    fs::remove_file(output_path)?;
+    Ok(lines)
+}
+
+#[test]
+fn test_unique_sort() -> Result<(), anyhow::Error> {
+    common::setup();
+    let first = common::keyed_lines(2000, 50, 1);
+    let second = common::keyed_lines(1000, 50, 2);
+    let input_files = vec![common::write_input(&first)?, common::write_input(&second)?];
+    let all: Vec<String> = first.iter().chain(second.iter()).cloned().collect();
+
+    let mut keep_first: Vec<String> = Vec::new();
+    for line in &all {
+        if !keep_first.iter().any(|kept| key(kept) == key(line)) {
+            keep_first.push(line.clone());
+        }
+    }
+    keep_first.sort_by_key(|line| key(line));
+    assert_eq!(unique_sort(input_files.clone(), Unique::KeepFirst, Order::Asc)?, keep_first);
+
+    let mut keep_last: Vec<String> = Vec::new();
+    for line in all.iter().rev() {
+        if !keep_last.iter().any(|kept| key(kept) == key(line)) {
+            keep_last.push(line.clone());
+        }
+    }
+    keep_last.sort_by_key(|line| -key(line));
+    assert_eq!(unique_sort(input_files.clone(), Unique::KeepLast, Order::Desc)?, keep_last);
+
+    let keep_any = unique_sort(input_files.clone(), Unique::KeepAny, Order::Asc)?;
+    assert_eq!(keep_any.len(), 50);
+    assert!(keep_any.iter().all(|line| all.contains(line)));
+
+    for path in input_files {
+        fs::remove_file(path)?;
+    }
+    Ok(())
+}
+
+#[test]
+fn test_unique_merge() -> Result<(), anyhow::Error> {
+    common::setup();
+    let input_path = common::write_input(&["1\ta".to_string(), "1\tb".to_string(), "2\tc".to_string()])?;
+    let output_path = common::temp_file_name("./target/results/");
+
+    let mut text_file_sort = Sort::new(vec![input_path.clone(), input_path.clone()], output_path.clone());
+    text_file_sort.add_field(Field::new(1, FieldType::Integer));
+    text_file_sort.with_unique(Unique::KeepLast);
+    text_file_sort.with_tmp_dir(PathBuf::from("./target/results/"));
+    text_file_sort.merge()?;
+    assert_eq!(common::read_lines(output_path.clone())?, vec!["1\tb", "2\tc"]);
+
+    let mut text_file_sort = Sort::new(vec![input_path.clone()], output_path.clone());
+    text_file_sort.add_field(Field::new(1, FieldType::Integer));
+    text_file_sort.with_unique(Unique::KeepFirst);
+    text_file_sort.with_tmp_dir(PathBuf::from("./target/results/"));
+    text_file_sort.merge()?;
+    assert_eq!(common::read_lines(output_path.clone())?, vec!["1\ta", "2\tc"]);
+
+    let mut check = Sort::new(vec![input_path.clone()], PathBuf::new());
+    check.add_field(Field::new(1, FieldType::Integer));
+    check.with_unique(Unique::KeepAny);
+    assert!(!check.check()?);
+
+    fs::remove_file(input_path)?;
+    fs::remove_file(output_path)?;
+    Ok(())
+}
@@ -0,0 +1,100 @@
+use std::fs;
+use std::path::PathBuf;
+use text_file_sort::field::Field;
+use text_file_sort::field_type::FieldType;
+use text_file_sort::sort::Sort;
+use text_file_sort::unique::Unique;
+
+mod common;
+
+const BASE: [&str; 5] = [
+    "1\tbase",
+    "2\tbase",
+    "3\tbase",
+    "5\tbase",
+    "8\tbase",
+];
+
+// modifies 2 and 8, deletes 3 and the missing 4, adds 6
+const CHANGE: [&str; 5] = [
+    "2\tchange",
+    "3\tdeleted",
+    "4\tdeleted",
+    "6\tchange",
+    "8\tchange",
+];
+
+const EXPECTED: [&str; 5] = [
+    "1\tbase",
+    "2\tchange",
+    "5\tbase",
+    "6\tchange",
+    "8\tchange",
+];
+
+fn upsert(input_files: Vec<PathBuf>, priorities: Vec<usize>, merge: bool) -> Result<Vec<String>, anyhow::Error> {
+    let output_path = common::temp_file_name("./target/results/");
+    let mut text_file_sort = Sort::new(input_files, output_path.clone());
+    text_file_sort.add_field(Field::new(1, FieldType::Integer));
+    text_file_sort.with_priorities(priorities);
+    text_file_sort.with_tombstone(|line| line.ends_with("\tdeleted"));
+    text_file_sort.with_tasks(2);
+    text_file_sort.with_chunk_size_bytes(16);
+    text_file_sort.with_tmp_dir(PathBuf::from("./target/results/"));
+    if merge {
+        text_file_sort.merge()?;
+    } else {
+        text_file_sort.sort()?;
+    }
+    let lines = common::read_lines(output_path.clone())?;
+    fs::remove_file(output_path)?;
+    Ok(lines)
+}
+
+#[test]
+fn test_upsert_merge() -> Result<(), anyhow::Error> {
+    common::setup();
+    let base = common::write_input(&BASE)?;
+    let change = common::write_input(&CHANGE)?;
+    assert_eq!(upsert(vec![base.clone(), change.clone()], vec![0, 1], true)?, EXPECTED);
+    assert_eq!(upsert(vec![change.clone(), base.clone()], vec![1, 0], true)?, EXPECTED);
+    // inputs of the same priority, the last one in the input order wins
+    assert_eq!(upsert(vec![base.clone(), change.clone()], vec![], true)?, EXPECTED);
+
+    // the base wins, only tombstones of keys missing from the base are dropped
+    let expected = vec!["1\tbase", "2\tbase", "3\tbase", "5\tbase", "6\tchange", "8\tbase"];
+    assert_eq!(upsert(vec![base.clone(), change.clone()], vec![1, 0], true)?, expected);
+    fs::remove_file(base)?;
+    fs::remove_file(change)?;
+    Ok(())
+}
+
+#[test]
+fn test_upsert_sort() -> Result<(), anyhow::Error> {
+    common::setup();
+    let mut base: Vec<&str> = BASE.to_vec();
+    base.reverse();
+    let mut change: Vec<&str> = CHANGE.to_vec();
+    change.reverse();
+    let base = common::write_input(&base)?;
+    let change = common::write_input(&change)?;
+    assert_eq!(upsert(vec![base.clone(), change.clone()], vec![0, 1], false)?, EXPECTED);
+    fs::remove_file(base)?;
+    fs::remove_file(change)?;
+    Ok(())
+}
+
+#[test]
+fn test_upsert_check() -> Result<(), anyhow::Error> {
+    common::setup();
+    // a change file may hold several records with the same key
+    let input = common::write_input(&["1\tbase", "2\tbase", "2\tchange", "3\tbase"])?;
+    let mut text_file_sort = Sort::new(vec![input.clone()], PathBuf::new());
+    text_file_sort.add_field(Field::new(1, FieldType::Integer));
+    text_file_sort.with_priorities(vec![0]);
+    assert!(text_file_sort.check()?);
+    text_file_sort.with_unique(Unique::KeepLast);
+    assert!(!text_file_sort.check()?);
+    fs::remove_file(input)?;
+    Ok(())
+}
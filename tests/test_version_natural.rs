@@ -0,0 +1,39 @@
+use text_file_sort::field::Field;
+use text_file_sort::field_type::FieldType;
+use text_file_sort::order::Order;
+
+mod common;
+
+#[test]
+fn test_version() -> Result<(), anyhow::Error> {
+    let lines = common::sort_lines(
+        &["pkg\t1.10.0", "pkg\t1.2.0", "pkg\t1.10.0~rc1", "pkg\t1.9", "pkg\t1.10.0a"],
+        vec![Field::new(2, FieldType::Version)],
+        Order::Asc,
+    )?;
+    assert_eq!(lines, vec!["pkg\t1.2.0", "pkg\t1.9", "pkg\t1.10.0~rc1", "pkg\t1.10.0", "pkg\t1.10.0a"]);
+    Ok(())
+}
+
+#[test]
+fn test_natural() -> Result<(), anyhow::Error> {
+    let lines = common::sort_lines(
+        &["1\troute 10", "2\tRoute 9", "3\tRoute 100", "4\tRoute 9a"],
+        vec![Field::new(2, FieldType::Natural).with_ignore_case(true)],
+        Order::Asc,
+    )?;
+    assert_eq!(lines, vec!["2\tRoute 9", "4\tRoute 9a", "1\troute 10", "3\tRoute 100"]);
+    Ok(())
+}
+
+#[test]
+fn test_version_whole_line() -> Result<(), anyhow::Error> {
+    // the line terminator is not a part of the version
+    let lines = common::sort_lines(
+        &["1.10.0a", "1.10.0", "1.9", "1.10.0~rc1"],
+        vec![Field::new(0, FieldType::Version)],
+        Order::Asc,
+    )?;
+    assert_eq!(lines, vec!["1.9", "1.10.0~rc1", "1.10.0", "1.10.0a"]);
+    Ok(())
+}